@@ -1,3 +1,4 @@
+#![cfg(feature = "unstable")]
 #![feature(test)]
 extern crate test;
 
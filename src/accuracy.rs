@@ -0,0 +1,98 @@
+//! Position accuracy estimates from GST pseudorange error statistics.
+
+use core::f64::consts::PI;
+use alloc::vec::Vec;
+use libm;
+
+use parse::{GgaData, GstData};
+
+/// Scale of one sigma error ellipse to 95% confidence:
+/// square root of chi-squared 95% quantile with 2 degrees of freedom
+const SCALE_95: f32 = 2.4477;
+
+/// WGS84 semi-major axis, meters
+const EARTH_RADIUS: f64 = 6_378_137.;
+
+/// Horizontal error ellipse, meters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorEllipse {
+    pub semi_major: f32,
+    pub semi_minor: f32,
+    /// Orientation of semi-major axis, degrees from true north
+    pub orientation: f32,
+}
+
+impl ErrorEllipse {
+    fn scale(&self, k: f32) -> ErrorEllipse {
+        ErrorEllipse {
+            semi_major: self.semi_major * k,
+            semi_minor: self.semi_minor * k,
+            orientation: self.orientation,
+        }
+    }
+
+    /// Point of the ellipse border, as meters north and east of its center.
+    /// `angle` is in radians, 0 is the end of semi-major axis.
+    pub fn offset(&self, angle: f64) -> (f64, f64) {
+        let (a, b) = (self.semi_major as f64, self.semi_minor as f64);
+        let theta = (self.orientation as f64).to_radians();
+        let (x, y) = (a * libm::cos(angle), b * libm::sin(angle));
+        let (sin_theta, cos_theta) = (libm::sin(theta), libm::cos(theta));
+        (x * cos_theta - y * sin_theta, x * sin_theta + y * cos_theta)
+    }
+
+    /// Border of the ellipse around position of GGA fix, as `points`
+    /// latitude/longitude pairs in degrees. `None` if GGA has no position.
+    ///
+    /// Uses local flat earth approximation, which is fine for errors
+    /// of up to several kilometers.
+    pub fn outline(&self, gga: &GgaData, points: usize) -> Option<Vec<(f64, f64)>> {
+        let (lat, lon) = match (gga.latitude, gga.longitude) {
+            (Some(lat), Some(lon)) => (lat, lon),
+            _ => return None,
+        };
+        let cos_lat = libm::cos(lat.to_radians());
+        Some((0..points)
+                 .map(|i| {
+                          let (north, east) = self.offset(2. * PI * i as f64 / points as f64);
+                          (lat + (north / EARTH_RADIUS).to_degrees(),
+                           lon + (east / (EARTH_RADIUS * cos_lat)).to_degrees())
+                      })
+                 .collect())
+    }
+}
+
+impl GstData {
+    /// One sigma error ellipse
+    pub fn error_ellipse(&self) -> Option<ErrorEllipse> {
+        match (self.semi_major, self.semi_minor, self.orientation) {
+            (Some(semi_major), Some(semi_minor), Some(orientation)) => {
+                Some(ErrorEllipse {
+                         semi_major,
+                         semi_minor,
+                         orientation,
+                     })
+            }
+            _ => None,
+        }
+    }
+
+    /// Error ellipse containing the position with 95% probability
+    pub fn error_ellipse_95(&self) -> Option<ErrorEllipse> {
+        self.error_ellipse().map(|v| v.scale(SCALE_95))
+    }
+
+    /// Radius of circle containing the position with 95% probability, meters
+    ///
+    /// Computed from latitude and longitude sigmas, or from the error ellipse
+    /// axes if they are missing. Exact for circular error distribution,
+    /// good approximation unless the ellipse is very elongated.
+    pub fn horizontal_error_95(&self) -> Option<f32> {
+        let (a, b) = match (self.lat_error, self.lon_error, self.semi_major, self.semi_minor) {
+            (Some(lat), Some(lon), _, _) => (lat, lon),
+            (_, _, Some(major), Some(minor)) => (major, minor),
+            _ => return None,
+        };
+        Some(SCALE_95 * libm::sqrtf((a * a + b * b) / 2.))
+    }
+}
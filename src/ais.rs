@@ -0,0 +1,698 @@
+//! AIS messages encapsulated in VDM and VDO sentences.
+//!
+//! AIS message is a bit string, sent as 6-bit ASCII armoured payload
+//! of one or more `!AIVDM` sentences. Fragments of one message share
+//! the sequential message ID and the radio channel.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use page::{PageError, PageSequence};
+use parse::VdmData;
+use time::{NaiveDate, NaiveTime, NaiveDateTime};
+
+/// Decoded AIS payload, bits in transmission order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BitBuffer {
+    data: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    /// Number of bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Value of one bit, `None` if `idx` is out of range
+    pub fn bit(&self, idx: usize) -> Option<bool> {
+        if idx >= self.len {
+            return None;
+        }
+        Some(self.data[idx / 8] & (0x80 >> (idx % 8)) != 0)
+    }
+
+    /// Unsigned integer of `len` bits, most significant bit first,
+    /// `None` if the field doesn't fit into the buffer or into 32 bits
+    pub fn unsigned(&self, start: usize, len: usize) -> Option<u32> {
+        if len > 32 || start + len > self.len {
+            return None;
+        }
+        Some((start..start + len).fold(0, |acc, idx| {
+            (acc << 1) | self.bit(idx).unwrap() as u32
+        }))
+    }
+
+    /// Two's complement signed integer of `len` bits
+    pub fn signed(&self, start: usize, len: usize) -> Option<i32> {
+        if len == 0 {
+            return None;
+        }
+        self.unsigned(start, len)
+            .map(|v| ((v << (32 - len)) as i32) >> (32 - len))
+    }
+
+    /// Text of `len` bits in AIS 6-bit ASCII, `@` padding and trailing
+    /// spaces removed. Only complete characters inside the buffer are
+    /// decoded.
+    pub fn text(&self, start: usize, len: usize) -> Option<String> {
+        if start >= self.len {
+            return None;
+        }
+        let len = len.min(self.len - start);
+        let mut text = String::with_capacity(len / 6);
+        for idx in 0..len / 6 {
+            let c = self.unsigned(start + idx * 6, 6).unwrap() as u8;
+            if c == 0 {
+                break;
+            }
+            text.push(if c < 32 { (c + 64) as char } else { c as char });
+        }
+        let trimmed = text.trim_end().len();
+        text.truncate(trimmed);
+        Some(text)
+    }
+
+    /// Unsigned field, callers check the buffer length first
+    fn uint(&self, start: usize, len: usize) -> u32 {
+        self.unsigned(start, len).unwrap_or(0)
+    }
+
+    /// Signed field, callers check the buffer length first
+    fn int(&self, start: usize, len: usize) -> i32 {
+        self.signed(start, len).unwrap_or(0)
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]
+    fn push(&mut self, value: u8, bits: usize) {
+        for i in (0..bits).rev() {
+            if self.len % 8 == 0 {
+                self.data.push(0);
+            }
+            if value & (1 << i) != 0 {
+                let last = self.data.len() - 1;
+                self.data[last] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AisError {
+    /// Payload contains character outside of 6-bit ASCII armouring
+    InvalidPayload(u8),
+    /// Fill bits don't fit into the payload
+    InvalidFillBits,
+    /// Fragment number is zero or greater than number of fragments
+    InvalidFragmentNum,
+    /// The fragment is the same as the previous one, it was ignored
+    DuplicateFragment(u8),
+    /// The fragment belongs to the current message but was already
+    /// received, it was ignored
+    FragmentOutOfOrder { expected: u8, received: u8 },
+    /// Fragments between `expected` and `received` never arrived,
+    /// current message was dropped
+    MissingFragment { expected: u8, received: u8 },
+    /// Number of fragments changed in the middle of the message,
+    /// current message was dropped
+    FragmentCountChanged { expected: u8, received: u8 },
+    /// Message is too short to contain message type
+    Empty,
+    /// Message is shorter than its type requires
+    TooShort { message_type: u8, len: usize },
+    /// Decoding of this message type is not supported
+    UnsupportedType(u8),
+}
+
+impl From<PageError> for AisError {
+    fn from(err: PageError) -> AisError {
+        match err {
+            PageError::InvalidSentenceNum => AisError::InvalidFragmentNum,
+            PageError::DuplicatePage(num) => AisError::DuplicateFragment(num as u8),
+            PageError::OutOfOrder { expected, received } => {
+                AisError::FragmentOutOfOrder {
+                    expected: expected as u8,
+                    received: received as u8,
+                }
+            }
+            PageError::MissingPage { expected, received } => {
+                AisError::MissingFragment {
+                    expected: expected as u8,
+                    received: received as u8,
+                }
+            }
+            PageError::PageCountChanged { expected, received } => {
+                AisError::FragmentCountChanged {
+                    expected: expected as u8,
+                    received: received as u8,
+                }
+            }
+        }
+    }
+}
+
+/// De-armour 6-bit ASCII payload, dropping `fill_bits` from the end
+pub fn dearmor(payload: &[u8], fill_bits: u8) -> Result<BitBuffer, AisError> {
+    let mut bits = BitBuffer::default();
+    dearmor_into(&mut bits, payload)?;
+    strip_fill_bits(&mut bits, fill_bits)?;
+    Ok(bits)
+}
+
+fn dearmor_into(bits: &mut BitBuffer, payload: &[u8]) -> Result<(), AisError> {
+    for &c in payload {
+        let value = match c {
+            b'0'..=b'W' => c - b'0',
+            b'`'..=b'w' => c - b'0' - 8,
+            _ => return Err(AisError::InvalidPayload(c)),
+        };
+        bits.push(value, 6);
+    }
+    Ok(())
+}
+
+#[allow(clippy::manual_div_ceil, clippy::manual_is_multiple_of)]
+fn strip_fill_bits(bits: &mut BitBuffer, fill_bits: u8) -> Result<(), AisError> {
+    let fill_bits = fill_bits as usize;
+    if fill_bits > 5 || fill_bits > bits.len {
+        return Err(AisError::InvalidFillBits);
+    }
+    bits.len -= fill_bits;
+    bits.data.truncate((bits.len + 7) / 8);
+    if bits.len % 8 != 0 {
+        let last = bits.data.len() - 1;
+        bits.data[last] &= 0xff << (8 - bits.len % 8);
+    }
+    Ok(())
+}
+
+/// Complete AIS message
+#[derive(Debug, Clone, PartialEq)]
+pub struct AisMessage {
+    /// Received from VDO, report of own vessel
+    pub own_vessel: bool,
+    pub channel: Option<char>,
+    pub message_type: u8,
+    pub bits: BitBuffer,
+}
+
+impl AisMessage {
+    /// Decode the message into typed report
+    pub fn decode(&self) -> Result<AisReport, AisError> {
+        let bits = &self.bits;
+        let min_len = match self.message_type {
+            1..=3 => 143,
+            4 => 134,
+            5 => 420,
+            18 => 139,
+            19 => 305,
+            21 => 272,
+            24 if bits.unsigned(38, 2) == Some(0) => 160,
+            24 => 162,
+            other => return Err(AisError::UnsupportedType(other)),
+        };
+        if bits.len() < min_len {
+            return Err(AisError::TooShort {
+                           message_type: self.message_type,
+                           len: bits.len(),
+                       });
+        }
+        let mmsi = bits.uint(8, 30);
+        Ok(match self.message_type {
+               1..=3 => AisReport::Position(decode_position(self.message_type, mmsi, bits)),
+               4 => AisReport::BaseStation(decode_base_station(mmsi, bits)),
+               5 => AisReport::StaticVoyage(decode_static_voyage(mmsi, bits)),
+               18 | 19 => {
+                   AisReport::ClassBPosition(decode_class_b_position(self.message_type,
+                                                                     mmsi,
+                                                                     bits))
+               }
+               21 => AisReport::AidToNavigation(decode_aid_to_navigation(mmsi, bits)),
+               _ => AisReport::ClassBStatic(decode_class_b_static(mmsi, bits)),
+           })
+    }
+
+    fn new(own_vessel: bool, channel: Option<char>, bits: BitBuffer) -> Result<AisMessage, AisError> {
+        let message_type = bits.unsigned(0, 6).ok_or(AisError::Empty)? as u8;
+        Ok(AisMessage {
+               own_vessel,
+               channel,
+               message_type,
+               bits,
+           })
+    }
+}
+
+/// Joins fragments of multi-sentence AIS messages
+///
+/// Fragments are matched by sequential message ID, radio channel
+/// and VDM/VDO, so messages interleaved on channels A and B are
+/// assembled separately.
+#[derive(Default)]
+pub struct AisAssembler {
+    pending: PageSequence<(bool, Option<char>, Option<u8>), BitBuffer>,
+}
+
+impl AisAssembler {
+    pub fn new() -> AisAssembler {
+        AisAssembler::default()
+    }
+
+    /// Feed one parsed VDM or VDO sentence. Returns the message
+    /// if this was its last fragment.
+    pub fn add(&mut self, data: VdmData) -> Result<Option<AisMessage>, AisError> {
+        if data.fragment_num == 0 || data.fragment_num > data.fragment_count {
+            return Err(AisError::InvalidFragmentNum);
+        }
+        if data.fragment_count == 1 {
+            let bits = dearmor(&data.payload, data.fill_bits)?;
+            return AisMessage::new(data.own_vessel, data.channel, bits).map(Some);
+        }
+
+        let payload = data.payload;
+        let (_, bits) = self.pending
+            .add((data.own_vessel, data.channel, data.message_id),
+                 data.fragment_num as u16,
+                 data.fragment_count as u16,
+                 BitBuffer::default,
+                 |bits| dearmor_into(bits, &payload))?;
+        let mut bits = match bits {
+            Some(bits) => bits,
+            None => return Ok(None),
+        };
+        strip_fill_bits(&mut bits, data.fill_bits)?;
+        AisMessage::new(data.own_vessel, data.channel, bits).map(Some)
+    }
+}
+
+/// Navigational status of Class A vessel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NavigationStatus {
+    UnderWayUsingEngine,
+    AtAnchor,
+    NotUnderCommand,
+    RestrictedManoeuvrability,
+    ConstrainedByDraught,
+    Moored,
+    Aground,
+    EngagedInFishing,
+    UnderWaySailing,
+    AisSartActive,
+    /// Values reserved for future use
+    Reserved(u8),
+}
+
+impl NavigationStatus {
+    /// `None` for 15, "not defined"
+    fn from_ais(x: u32) -> Option<NavigationStatus> {
+        Some(match x {
+                 0 => NavigationStatus::UnderWayUsingEngine,
+                 1 => NavigationStatus::AtAnchor,
+                 2 => NavigationStatus::NotUnderCommand,
+                 3 => NavigationStatus::RestrictedManoeuvrability,
+                 4 => NavigationStatus::ConstrainedByDraught,
+                 5 => NavigationStatus::Moored,
+                 6 => NavigationStatus::Aground,
+                 7 => NavigationStatus::EngagedInFishing,
+                 8 => NavigationStatus::UnderWaySailing,
+                 14 => NavigationStatus::AisSartActive,
+                 15 => return None,
+                 x => NavigationStatus::Reserved(x as u8),
+             })
+    }
+}
+
+/// Rate of turn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateOfTurn {
+    /// Degrees per minute, positive to starboard
+    Rate(f32),
+    /// Turning to starboard at more than 5 degrees per 30 seconds,
+    /// no turn indicator available
+    FastStarboard,
+    /// Turning to port at more than 5 degrees per 30 seconds,
+    /// no turn indicator available
+    FastPort,
+}
+
+impl RateOfTurn {
+    /// `None` for -128, "not available"
+    fn from_ais(x: i32) -> Option<RateOfTurn> {
+        match x {
+            -128 => None,
+            127 => Some(RateOfTurn::FastStarboard),
+            -127 => Some(RateOfTurn::FastPort),
+            x => {
+                let rate = x as f32 / 4.733;
+                Some(RateOfTurn::Rate(if x < 0 { -rate * rate } else { rate * rate }))
+            }
+        }
+    }
+}
+
+/// Distances from the reference point of the position report
+/// to the ship ends, meters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dimensions {
+    pub to_bow: u16,
+    pub to_stern: u16,
+    pub to_port: u8,
+    pub to_starboard: u8,
+}
+
+impl Dimensions {
+    fn from_ais(bits: &BitBuffer, start: usize) -> Option<Dimensions> {
+        let dim = Dimensions {
+            to_bow: bits.uint(start, 9) as u16,
+            to_stern: bits.uint(start + 9, 9) as u16,
+            to_port: bits.uint(start + 18, 6) as u8,
+            to_starboard: bits.uint(start + 24, 6) as u8,
+        };
+        if dim == (Dimensions { to_bow: 0, to_stern: 0, to_port: 0, to_starboard: 0 }) {
+            None
+        } else {
+            Some(dim)
+        }
+    }
+
+    pub fn length(&self) -> u16 {
+        self.to_bow + self.to_stern
+    }
+
+    pub fn beam(&self) -> u8 {
+        self.to_port + self.to_starboard
+    }
+}
+
+/// Estimated time of arrival, UTC, fields are `None` if not available
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eta {
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+}
+
+/// Position report of Class A vessel, message types 1, 2 and 3
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionReport {
+    pub message_type: u8,
+    pub mmsi: u32,
+    pub nav_status: Option<NavigationStatus>,
+    pub rate_of_turn: Option<RateOfTurn>,
+    /// Knots
+    pub speed_over_ground: Option<f32>,
+    /// Position accuracy is better than 10 meters
+    pub high_accuracy: bool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Degrees true
+    pub course_over_ground: Option<f32>,
+    /// Degrees true
+    pub true_heading: Option<u16>,
+    /// Second of UTC minute of the report
+    pub timestamp: Option<u8>,
+}
+
+/// Base station report, message type 4
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaseStationReport {
+    pub mmsi: u32,
+    pub utc: Option<NaiveDateTime>,
+    /// Position accuracy is better than 10 meters
+    pub high_accuracy: bool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Static and voyage related data of Class A vessel, message type 5
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticVoyageData {
+    pub mmsi: u32,
+    pub ais_version: u8,
+    pub imo: Option<u32>,
+    pub callsign: Option<String>,
+    pub ship_name: Option<String>,
+    pub ship_type: Option<u8>,
+    pub dimensions: Option<Dimensions>,
+    pub eta: Eta,
+    /// Meters
+    pub draught: Option<f32>,
+    pub destination: Option<String>,
+}
+
+/// Position report of Class B vessel, message types 18 and 19.
+/// Ship name, type and dimensions are sent only in 19.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassBPositionReport {
+    pub message_type: u8,
+    pub mmsi: u32,
+    /// Knots
+    pub speed_over_ground: Option<f32>,
+    /// Position accuracy is better than 10 meters
+    pub high_accuracy: bool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Degrees true
+    pub course_over_ground: Option<f32>,
+    /// Degrees true
+    pub true_heading: Option<u16>,
+    /// Second of UTC minute of the report
+    pub timestamp: Option<u8>,
+    pub ship_name: Option<String>,
+    pub ship_type: Option<u8>,
+    pub dimensions: Option<Dimensions>,
+}
+
+/// Aid to navigation report, message type 21
+#[derive(Debug, Clone, PartialEq)]
+pub struct AidToNavigationReport {
+    pub mmsi: u32,
+    pub aid_type: u8,
+    /// Name, with name extension appended
+    pub name: Option<String>,
+    /// Position accuracy is better than 10 meters
+    pub high_accuracy: bool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub dimensions: Option<Dimensions>,
+    pub off_position: bool,
+    pub virtual_aid: bool,
+}
+
+/// Static data of Class B vessel, message type 24.
+/// Part A carries ship name, part B the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassBStaticData {
+    pub mmsi: u32,
+    /// 0 for part A, 1 for part B
+    pub part_number: u8,
+    pub ship_name: Option<String>,
+    pub ship_type: Option<u8>,
+    /// Manufacturer ID, three characters
+    pub vendor_id: Option<String>,
+    pub callsign: Option<String>,
+    pub dimensions: Option<Dimensions>,
+    /// MMSI of mother ship, sent by auxiliary craft instead of dimensions
+    pub mothership_mmsi: Option<u32>,
+}
+
+/// Decoded AIS message
+#[derive(Debug, Clone, PartialEq)]
+pub enum AisReport {
+    Position(PositionReport),
+    BaseStation(BaseStationReport),
+    StaticVoyage(StaticVoyageData),
+    ClassBPosition(ClassBPositionReport),
+    AidToNavigation(AidToNavigationReport),
+    ClassBStatic(ClassBStaticData),
+}
+
+fn non_empty(text: Option<String>) -> Option<String> {
+    text.and_then(|v| if v.is_empty() { None } else { Some(v) })
+}
+
+/// Optional field, `None` if it holds "not available" value
+fn available(value: u32, not_available: u32) -> Option<u32> {
+    if value == not_available {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Longitude and latitude in 1/10000 minutes, 181 and 91 degrees
+/// mean not available
+fn position(bits: &BitBuffer, start: usize) -> (Option<f64>, Option<f64>) {
+    let lon = bits.int(start, 28) as f64 / 600_000.;
+    let lat = bits.int(start + 28, 27) as f64 / 600_000.;
+    (if lat.abs() <= 90. { Some(lat) } else { None },
+     if lon.abs() <= 180. { Some(lon) } else { None })
+}
+
+fn speed(bits: &BitBuffer, start: usize) -> Option<f32> {
+    available(bits.uint(start, 10), 1023).map(|v| v as f32 / 10.)
+}
+
+fn course(bits: &BitBuffer, start: usize) -> Option<f32> {
+    let x = bits.uint(start, 12);
+    if x < 3600 { Some(x as f32 / 10.) } else { None }
+}
+
+fn heading(bits: &BitBuffer, start: usize) -> Option<u16> {
+    let x = bits.uint(start, 9);
+    if x < 360 { Some(x as u16) } else { None }
+}
+
+fn timestamp(bits: &BitBuffer, start: usize) -> Option<u8> {
+    let x = bits.uint(start, 6);
+    if x < 60 { Some(x as u8) } else { None }
+}
+
+fn ship_type(bits: &BitBuffer, start: usize) -> Option<u8> {
+    available(bits.uint(start, 8), 0).map(|v| v as u8)
+}
+
+fn decode_position(message_type: u8, mmsi: u32, bits: &BitBuffer) -> PositionReport {
+    let (latitude, longitude) = position(bits, 61);
+    PositionReport {
+        message_type,
+        mmsi,
+        nav_status: NavigationStatus::from_ais(bits.uint(38, 4)),
+        rate_of_turn: RateOfTurn::from_ais(bits.int(42, 8)),
+        speed_over_ground: speed(bits, 50),
+        high_accuracy: bits.uint(60, 1) == 1,
+        latitude,
+        longitude,
+        course_over_ground: course(bits, 116),
+        true_heading: heading(bits, 128),
+        timestamp: timestamp(bits, 137),
+    }
+}
+
+fn decode_base_station(mmsi: u32, bits: &BitBuffer) -> BaseStationReport {
+    let date = match (bits.uint(38, 14), bits.uint(52, 4), bits.uint(56, 5)) {
+        (0, _, _) | (_, 0, _) | (_, _, 0) => None,
+        (year, month, day) => {
+            Some(NaiveDate {
+                     year: year as i32,
+                     month,
+                     day,
+                 })
+        }
+    };
+    let time = match (bits.uint(61, 5), bits.uint(66, 6), bits.uint(72, 6)) {
+        (hour, min, sec) if hour < 24 && min < 60 && sec < 60 => {
+            Some(NaiveTime {
+                     hour,
+                     min,
+                     sec: sec as f64,
+                 })
+        }
+        _ => None,
+    };
+    let (latitude, longitude) = position(bits, 79);
+    BaseStationReport {
+        mmsi,
+        utc: match (date, time) {
+            (Some(date), Some(time)) => Some(NaiveDateTime { date, time }),
+            _ => None,
+        },
+        high_accuracy: bits.uint(78, 1) == 1,
+        latitude,
+        longitude,
+    }
+}
+
+fn decode_static_voyage(mmsi: u32, bits: &BitBuffer) -> StaticVoyageData {
+    StaticVoyageData {
+        mmsi,
+        ais_version: bits.uint(38, 2) as u8,
+        imo: available(bits.uint(40, 30), 0),
+        callsign: non_empty(bits.text(70, 42)),
+        ship_name: non_empty(bits.text(112, 120)),
+        ship_type: ship_type(bits, 232),
+        dimensions: Dimensions::from_ais(bits, 240),
+        eta: Eta {
+            month: available(bits.uint(274, 4), 0).map(|v| v as u8),
+            day: available(bits.uint(278, 5), 0).map(|v| v as u8),
+            hour: available(bits.uint(283, 5), 24).map(|v| v as u8),
+            minute: available(bits.uint(288, 6), 60).map(|v| v as u8),
+        },
+        draught: available(bits.uint(294, 8), 0).map(|v| v as f32 / 10.),
+        destination: non_empty(bits.text(302, 120)),
+    }
+}
+
+fn decode_class_b_position(message_type: u8, mmsi: u32, bits: &BitBuffer) -> ClassBPositionReport {
+    let (latitude, longitude) = position(bits, 57);
+    let extended = message_type == 19;
+    ClassBPositionReport {
+        message_type,
+        mmsi,
+        speed_over_ground: speed(bits, 46),
+        high_accuracy: bits.uint(56, 1) == 1,
+        latitude,
+        longitude,
+        course_over_ground: course(bits, 112),
+        true_heading: heading(bits, 124),
+        timestamp: timestamp(bits, 133),
+        ship_name: if extended { non_empty(bits.text(143, 120)) } else { None },
+        ship_type: if extended { ship_type(bits, 263) } else { None },
+        dimensions: if extended { Dimensions::from_ais(bits, 271) } else { None },
+    }
+}
+
+fn decode_aid_to_navigation(mmsi: u32, bits: &BitBuffer) -> AidToNavigationReport {
+    let mut name = bits.text(43, 120).unwrap_or_default();
+    if name.len() == 20 {
+        if let Some(extension) = bits.text(272, 88) {
+            name.push_str(&extension);
+        }
+    }
+    let (latitude, longitude) = position(bits, 164);
+    AidToNavigationReport {
+        mmsi,
+        aid_type: bits.uint(38, 5) as u8,
+        name: non_empty(Some(name)),
+        high_accuracy: bits.uint(163, 1) == 1,
+        latitude,
+        longitude,
+        dimensions: Dimensions::from_ais(bits, 219),
+        off_position: bits.uint(259, 1) == 1,
+        virtual_aid: bits.uint(269, 1) == 1,
+    }
+}
+
+fn decode_class_b_static(mmsi: u32, bits: &BitBuffer) -> ClassBStaticData {
+    let part_number = bits.uint(38, 2) as u8;
+    let mut data = ClassBStaticData {
+        mmsi,
+        part_number,
+        ship_name: None,
+        ship_type: None,
+        vendor_id: None,
+        callsign: None,
+        dimensions: None,
+        mothership_mmsi: None,
+    };
+    if part_number == 0 {
+        data.ship_name = non_empty(bits.text(40, 120));
+    } else {
+        data.ship_type = ship_type(bits, 40);
+        data.vendor_id = non_empty(bits.text(48, 18));
+        data.callsign = non_empty(bits.text(90, 42));
+        // auxiliary craft, MMSI 98XXXYYYY, send mother ship MMSI instead of dimensions
+        if mmsi / 10_000_000 == 98 {
+            data.mothership_mmsi = available(bits.uint(132, 30), 0);
+        } else {
+            data.dimensions = Dimensions::from_ais(bits, 132);
+        }
+    }
+    data
+}
@@ -0,0 +1,125 @@
+//! Per-satellite statistics of GRS range residuals.
+//!
+//! GRS lists residuals in the order of satellite fields in GSA, so every GRS
+//! has to be paired with GSA of the same epoch and constellation.
+//! Satellites affected by multipath stand out by large residuals.
+
+use alloc::collections::BTreeMap;
+use libm;
+
+use GnssType;
+use parse::{GrsData, GsaData};
+use prn;
+
+/// Residual statistics of one satellite, meters
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatelliteResiduals {
+    pub gnss_type: GnssType,
+    /// PRN as reported in GSA
+    pub prn: u32,
+    /// Native satellite number, see `prn::from_nmea_prn`
+    pub svid: u32,
+    /// Number of residuals collected
+    pub count: u32,
+    pub mean: f32,
+    /// Root mean square
+    pub rms: f32,
+    pub std_dev: f32,
+    /// Largest absolute residual
+    pub max_abs: f32,
+}
+
+struct Accumulator {
+    prn: u32,
+    count: u32,
+    sum: f64,
+    sum_sq: f64,
+    max_abs: f32,
+}
+
+/// Collects GRS residuals per satellite
+#[derive(Default)]
+pub struct ResidualStatistics {
+    sats: BTreeMap<(GnssType, u32), Accumulator>,
+}
+
+impl ResidualStatistics {
+    pub fn new() -> ResidualStatistics {
+        ResidualStatistics::default()
+    }
+
+    /// Add residuals of GRS, paired with PRNs of GSA from the same epoch and
+    /// constellation. Residuals of satellites that can't be attributed to a
+    /// constellation are skipped. Returns number of residuals added.
+    pub fn add(&mut self, grs: &GrsData, gsa: &GsaData) -> usize {
+        let hint = grs.gnss_type.clone().or_else(|| gsa.gnss_type.clone());
+        let mut added = 0;
+        for (prn, residual) in grs.satellite_residuals(gsa) {
+            let key = match prn::classify(hint.clone(), prn) {
+                Some(key) => key,
+                None => continue,
+            };
+            let acc = self.sats
+                .entry(key)
+                .or_insert(Accumulator {
+                               prn,
+                               count: 0,
+                               sum: 0.,
+                               sum_sq: 0.,
+                               max_abs: 0.,
+                           });
+            acc.count += 1;
+            acc.sum += residual as f64;
+            acc.sum_sq += residual as f64 * residual as f64;
+            if residual.abs() > acc.max_abs {
+                acc.max_abs = residual.abs();
+            }
+            added += 1;
+        }
+        added
+    }
+
+    /// Statistics of one satellite, by its native satellite number
+    pub fn satellite(&self, gnss_type: &GnssType, svid: u32) -> Option<SatelliteResiduals> {
+        self.sats
+            .get(&(gnss_type.clone(), svid))
+            .map(|acc| summarize(gnss_type, svid, acc))
+    }
+
+    /// Statistics of all satellites, ordered by constellation and
+    /// satellite number
+    pub fn satellites<'a>(&'a self) -> impl Iterator<Item = SatelliteResiduals> + 'a {
+        self.sats
+            .iter()
+            .map(|(&(ref gnss_type, svid), acc)| summarize(gnss_type, svid, acc))
+    }
+
+    /// Satellites with RMS of residuals above `max_rms` meters,
+    /// likely affected by multipath
+    pub fn outliers<'a>(&'a self,
+                        max_rms: f32)
+                        -> impl Iterator<Item = SatelliteResiduals> + 'a {
+        self.satellites().filter(move |v| v.rms > max_rms)
+    }
+
+    pub fn clear(&mut self) {
+        self.sats.clear();
+    }
+}
+
+fn summarize(gnss_type: &GnssType, svid: u32, acc: &Accumulator) -> SatelliteResiduals {
+    let n = acc.count as f64;
+    let mean = acc.sum / n;
+    let mean_sq = acc.sum_sq / n;
+    let variance = mean_sq - mean * mean;
+    SatelliteResiduals {
+        gnss_type: gnss_type.clone(),
+        prn: acc.prn,
+        svid,
+        count: acc.count,
+        mean: mean as f32,
+        rms: libm::sqrt(mean_sq) as f32,
+        std_dev: libm::sqrt(if variance > 0. { variance } else { 0. }) as f32,
+        max_abs: acc.max_abs,
+    }
+}
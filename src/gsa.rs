@@ -0,0 +1,140 @@
+//! Merging of GSA sentences sent for one epoch.
+//!
+//! Multi-GNSS receivers send one GSA per constellation: GPGSA plus BDGSA,
+//! or several GNGSA told apart by system ID (NMEA 4.10) or by PRN ranges.
+//! DOPs are computed for the combined solution, so they should be the same
+//! in every GSA of the epoch.
+
+use alloc::vec::Vec;
+
+use GnssType;
+use parse::{GsaData, GsaMode1, GsaMode2};
+use prn;
+
+/// Satellite used for fix
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixSatellite {
+    pub gnss_type: GnssType,
+    /// PRN as reported in GSA
+    pub prn: u32,
+    /// Native satellite number, see `prn::from_nmea_prn`
+    pub svid: u32,
+}
+
+/// Satellites used for fix by all constellations, and DOPs of one epoch
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedGsa {
+    pub mode1: GsaMode1,
+    pub mode2: GsaMode2,
+    pub fix_sats: Vec<FixSatellite>,
+    /// PRNs that can't be attributed to any constellation
+    pub unknown_prns: Vec<u32>,
+    pub pdop: Option<f32>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    /// GSA sentences of this epoch carried different DOPs,
+    /// the ones from the first sentence are kept
+    pub dop_mismatch: bool,
+}
+
+impl MergedGsa {
+    /// Satellites of one constellation used for fix
+    pub fn fix_sats_of<'a>(&'a self,
+                           gnss_type: &'a GnssType)
+                           -> impl Iterator<Item = &'a FixSatellite> + 'a {
+        self.fix_sats.iter().filter(move |v| v.gnss_type == *gnss_type)
+    }
+}
+
+/// Collects GSA sentences of one epoch
+///
+/// GSA has no time field, so a new epoch is detected when a constellation
+/// repeats. Call `finish` when the epoch is known to be over, for example
+/// when the next GGA or RMC arrives.
+#[derive(Default)]
+pub struct GsaMerger {
+    current: Option<MergedGsa>,
+    constellations: Vec<GnssType>,
+}
+
+impl GsaMerger {
+    pub fn new() -> GsaMerger {
+        GsaMerger::default()
+    }
+
+    /// Add GSA sentence. Returns previous epoch, if this sentence starts
+    /// a new one.
+    pub fn add(&mut self, gsa: GsaData) -> Option<MergedGsa> {
+        let mut fix_sats = Vec::with_capacity(gsa.fix_sats_prn.len());
+        let mut unknown_prns = Vec::new();
+        for &prn in &gsa.fix_sats_prn {
+            match prn::classify(gsa.gnss_type.clone(), prn) {
+                Some((gnss_type, svid)) => fix_sats.push(FixSatellite { gnss_type, prn, svid }),
+                None => unknown_prns.push(prn),
+            }
+        }
+        let mut constellations: Vec<GnssType> = match gsa.gnss_type {
+            Some(ref gnss_type) => vec![gnss_type.clone()],
+            None => fix_sats.iter().map(|v| v.gnss_type.clone()).collect(),
+        };
+        constellations.sort();
+        constellations.dedup();
+
+        let prev = if constellations.iter().any(|v| self.constellations.contains(v)) {
+            self.finish()
+        } else {
+            None
+        };
+        self.constellations.extend(constellations);
+
+        match self.current {
+            None => {
+                self.current = Some(MergedGsa {
+                                        mode1: gsa.mode1,
+                                        mode2: gsa.mode2,
+                                        fix_sats,
+                                        unknown_prns,
+                                        pdop: gsa.pdop,
+                                        hdop: gsa.hdop,
+                                        vdop: gsa.vdop,
+                                        dop_mismatch: false,
+                                    })
+            }
+            Some(ref mut cur) => {
+                cur.mode2 = best_mode(&cur.mode2, &gsa.mode2);
+                cur.fix_sats.extend(fix_sats);
+                cur.unknown_prns.extend(unknown_prns);
+                cur.dop_mismatch |= merge_dop(&mut cur.pdop, gsa.pdop) |
+                                    merge_dop(&mut cur.hdop, gsa.hdop) |
+                                    merge_dop(&mut cur.vdop, gsa.vdop);
+            }
+        }
+        prev
+    }
+
+    /// Finish the current epoch
+    pub fn finish(&mut self) -> Option<MergedGsa> {
+        self.constellations.clear();
+        self.current.take()
+    }
+}
+
+fn best_mode(a: &GsaMode2, b: &GsaMode2) -> GsaMode2 {
+    match (a, b) {
+        (&GsaMode2::Fix3D, _) | (_, &GsaMode2::Fix3D) => GsaMode2::Fix3D,
+        (&GsaMode2::Fix2D, _) | (_, &GsaMode2::Fix2D) => GsaMode2::Fix2D,
+        _ => GsaMode2::NoFix,
+    }
+}
+
+/// Returns true if both values are present and differ
+fn merge_dop(cur: &mut Option<f32>, new: Option<f32>) -> bool {
+    match (*cur, new) {
+        (Some(a), Some(b)) => a != b,
+        (None, new) => {
+            *cur = new;
+            false
+        }
+        (Some(_), None) => false,
+    }
+}
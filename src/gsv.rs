@@ -0,0 +1,214 @@
+//! Assembly of multi-sentence GSV cycles into complete sky views.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use GnssType;
+use Satellite;
+use Signal;
+use parse::{GsvData, GsaData, GbsData};
+use page::{PageError, PageSequence, PageStatus};
+use prn;
+
+/// All satellites in view of one constellation and signal, gathered
+/// from every page of one GSV cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyView {
+    /// Constellation of the cycle, `None` for GNGSV cycles, which may mix
+    /// constellations
+    pub gnss_type: Option<GnssType>,
+    /// Signal ID of the cycle, NMEA 4.10 and later
+    pub signal_id: Option<u8>,
+    /// Number of satellites in view, as announced by the receiver
+    pub sats_in_view: u16,
+    pub satellites: Vec<Satellite>,
+}
+
+impl SkyView {
+    /// Decoded signal ID, see `Signal::from_nmea`. For GNGSV cycles
+    /// the constellation of the first satellite is used.
+    pub fn signal(&self) -> Option<Signal> {
+        let gnss_type = self.gnss_type
+            .clone()
+            .or_else(|| self.satellites.first().map(|v| v.gnss_type.clone()))?;
+        self.signal_id.and_then(|id| Signal::from_nmea(&gnss_type, id))
+    }
+}
+
+/// Result of feeding one GSV page into `GsvAssembler`, sky view is
+/// updated with `Complete`
+pub type GsvStatus = PageStatus;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GsvError {
+    /// Sentence number is zero or greater than number of sentences
+    InvalidSentenceNum,
+    /// The page is the same as the previous one, it was ignored
+    DuplicatePage(u16),
+    /// The page belongs to the current cycle but was already received,
+    /// it was ignored
+    OutOfOrder { expected: u16, received: u16 },
+    /// Pages between `expected` and `received` never arrived,
+    /// current cycle was dropped
+    MissingPage { expected: u16, received: u16 },
+    /// Number of sentences changed in the middle of the cycle,
+    /// current cycle was dropped
+    PageCountChanged { expected: u16, received: u16 },
+    /// All pages arrived, but the number of satellites differs from
+    /// the announced one. Sky view was updated anyway.
+    SatsInViewMismatch { announced: u16, received: u16 },
+}
+
+impl From<PageError> for GsvError {
+    fn from(err: PageError) -> GsvError {
+        match err {
+            PageError::InvalidSentenceNum => GsvError::InvalidSentenceNum,
+            PageError::DuplicatePage(page) => GsvError::DuplicatePage(page),
+            PageError::OutOfOrder { expected, received } => {
+                GsvError::OutOfOrder { expected, received }
+            }
+            PageError::MissingPage { expected, received } => {
+                GsvError::MissingPage { expected, received }
+            }
+            PageError::PageCountChanged { expected, received } => {
+                GsvError::PageCountChanged { expected, received }
+            }
+        }
+    }
+}
+
+struct GsvCycle {
+    sats_in_view: u16,
+    satellites: Vec<Satellite>,
+}
+
+/// Gathers pages of GPGSV/GLGSV/GAGSV/BDGSV cycles
+///
+/// Each talker and signal ID has its own cycle, so pages of GPGSV and
+/// GLGSV, or of GPGSV for L1 and L5, may be interleaved. GNGSV pages form
+/// one cycle of their own, whatever constellations their satellites
+/// belong to.
+///
+/// Satellites used for fix come from GSA sentences, see `update_used`.
+#[derive(Default)]
+pub struct GsvAssembler {
+    cycles: PageSequence<(Option<GnssType>, Option<u8>), GsvCycle>,
+    views: BTreeMap<(Option<GnssType>, Option<u8>), SkyView>,
+    /// Constellation and native number of satellites used for fix
+    used_sats: BTreeSet<(GnssType, u32)>,
+    /// The next GSA starts a new epoch
+    gsa_epoch_over: bool,
+}
+
+impl GsvAssembler {
+    pub fn new() -> GsvAssembler {
+        GsvAssembler::default()
+    }
+
+    /// Feed one parsed GSV page
+    pub fn add(&mut self, data: GsvData) -> Result<GsvStatus, GsvError> {
+        self.gsa_epoch_over = true;
+        let key = (data.gnss_type.clone(), data.signal_id);
+        let sats_in_view = data._sats_in_view;
+        let sats_info = data.sats_info;
+        let result: Result<_, GsvError> = self.cycles
+            .add(key.clone(),
+                 data.sentence_num,
+                 data.number_of_sentences,
+                 || {
+                     GsvCycle {
+                         sats_in_view,
+                         satellites: Vec::new(),
+                     }
+                 },
+                 |cycle| {
+                     cycle.satellites.extend(sats_info.iter().filter_map(|v| v.clone()));
+                     Ok(())
+                 });
+        let (status, cycle) = result?;
+        let cycle = match cycle {
+            Some(cycle) => cycle,
+            None => return Ok(status),
+        };
+
+        let mut view = SkyView {
+            gnss_type: data.gnss_type,
+            signal_id: data.signal_id,
+            sats_in_view: cycle.sats_in_view,
+            satellites: cycle.satellites,
+        };
+        mark_used(&mut view, &self.used_sats);
+        let received = view.satellites.len() as u16;
+        self.views.insert(key, view);
+        if received != cycle.sats_in_view {
+            return Err(GsvError::SatsInViewMismatch {
+                           announced: cycle.sats_in_view,
+                           received,
+                       });
+        }
+        Ok(status)
+    }
+
+    /// Remember satellites used for fix by one GSA sentence, and mark them
+    /// in the already assembled sky views
+    ///
+    /// Satellites are attributed to constellations by the GSA system ID or
+    /// talker id, and by PRN ranges for GNGSA without system ID. Satellites
+    /// of GSA sentences that follow each other are added up, as receivers
+    /// send one GSA per constellation and split more than 12 satellites
+    /// over several sentences. The first GSA after a GSV page, or after
+    /// `new_epoch`, replaces satellites of the previous epoch.
+    pub fn update_used(&mut self, gsa: &GsaData) {
+        if self.gsa_epoch_over {
+            self.used_sats.clear();
+            self.gsa_epoch_over = false;
+        }
+        self.used_sats.extend(gsa.fix_sats_prn
+                                  .iter()
+                                  .filter_map(|&prn| prn::classify(gsa.gnss_type.clone(), prn)));
+        for view in self.views.values_mut() {
+            mark_used(view, &self.used_sats);
+        }
+    }
+
+    /// Make the next GSA start a new epoch, for receivers that send GSV
+    /// less often than GSA. Call it when the epoch is known to be over, for
+    /// example when the next GGA or RMC arrives.
+    pub fn new_epoch(&mut self) {
+        self.gsa_epoch_over = true;
+    }
+
+    /// Latest complete sky view of the constellation and signal ID,
+    /// `gnss_type` is `None` for the GNGSV cycle
+    pub fn sky_view(&self,
+                    gnss_type: Option<GnssType>,
+                    signal_id: Option<u8>)
+                    -> Option<&SkyView> {
+        self.views.get(&(gnss_type, signal_id))
+    }
+
+    /// Latest complete sky views of all constellations and signals
+    pub fn sky_views(&self) -> impl Iterator<Item = &SkyView> {
+        self.views.values()
+    }
+
+    /// Satellite of the latest sky views that GBS reports as the most
+    /// likely failed one
+    pub fn failed_satellite(&self, gbs: &GbsData) -> Option<&Satellite> {
+        self.views
+            .values()
+            .flat_map(|view| view.satellites.iter())
+            .find(|sat| gbs.is_failed(sat))
+    }
+}
+
+/// Satellites of GNGSV cycles are matched by their PRN ranges, others
+/// by the constellation of the cycle, as GPGSV lists SBAS and QZSS
+/// satellites by their NMEA PRNs.
+fn mark_used(view: &mut SkyView, used_sats: &BTreeSet<(GnssType, u32)>) {
+    let SkyView { ref gnss_type, ref mut satellites, .. } = *view;
+    for sat in satellites.iter_mut() {
+        sat.used = prn::classify(gnss_type.clone(), sat.prn)
+            .is_some_and(|id| used_sats.contains(&id));
+    }
+}
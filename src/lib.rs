@@ -5,7 +5,6 @@
 //! to parse sentences without state
 //!
 //! Units that used every where: degrees, knots, meters for altitude
-#![feature(alloc)]
 // Copyright (C) 2016 Felix Obenhuber
 //
 // Licensed under the Apache License, Version 2.0 (the "License");
@@ -29,18 +28,506 @@ extern crate nom;
 #[cfg(test)]
 #[macro_use]
 extern crate approx;
+#[cfg(test)]
+#[macro_use]
+extern crate std;
 #[macro_use]
 extern crate alloc;
+extern crate libm;
 
 pub mod time;
 pub mod parse;
+pub mod gsv;
+pub mod gsa;
+pub mod prn;
+pub mod accuracy;
+pub mod page;
+pub mod route;
+pub mod ais;
+pub mod txt;
+pub mod grs;
+pub mod target;
 #[cfg(test)]
 mod test;
 
-use core::fmt;
-use alloc::prelude::*;
+use core::{fmt, mem};
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use alloc::collections::{BTreeMap, BTreeSet};
+
+pub use parse::{GsvData, GgaData, RmcData, RmcStatusOfFix, parse, ParseResult, GsaData, VtgData,
+                GllData, FaaMode, ZdaData, GstData, GnsData, NavigationalStatus,
+                GbsData, HdtData, HdmData, HdgData, ThsData, MwvData,
+                WindReference, MwdData, VwrData, DbtData, DbsData, DptData,
+                VhwData, VbwData, VlwData, SteerDirection, Bearing, XteData,
+                ApbData, RmbData, BwcData, BodData, WplData, RteData, RteMode,
+                VdmData, TxtData, TxtSeverity, DtmData, Datum, GrsData, GrsMode,
+                RotData, RsaData, XdrData, XdrMeasurement, XdrQuantity, TransducerType,
+                TransducerUnit, MtwData, MdaData, TtmData, TllData, OsdData, TargetStatus,
+                TargetAcquisition, SpeedReference, ParseError};
+pub use accuracy::ErrorEllipse;
+pub use page::{PageSequence, PageStatus, PageError};
+pub use gsv::{GsvAssembler, GsvStatus, GsvError, SkyView};
+pub use gsa::{GsaMerger, MergedGsa, FixSatellite};
+pub use route::{RouteAssembler, RouteStatus, RouteError, Route, Waypoint};
+pub use ais::{AisAssembler, AisMessage, AisError, AisReport, BitBuffer};
+pub use txt::{TxtAssembler, TxtMessage, TxtError, AntennaStatus};
+pub use grs::{ResidualStatistics, SatelliteResiduals};
+pub use target::{TargetTable, Target};
+use time::{NaiveDate, NaiveTime};
+
+/// ! NMEA parser
+///
+/// Keeps the latest merged state of all received sentences.
+#[derive(Debug, Clone, Default)]
+pub struct Nmea {
+    pub fix_time: Option<NaiveTime>,
+    pub fix_date: Option<NaiveDate>,
+    pub fix_type: Option<FixType>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub altitude: Option<f32>,
+    pub speed_over_ground: Option<f32>,
+    pub true_course: Option<f32>,
+    pub num_of_fix_satellites: Option<u32>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    pub pdop: Option<f32>,
+    pub geoid_height: Option<f32>,
+    pub fix_satellites_prns: Option<Vec<u32>>,
+    /// Datum of latitude, longitude and altitude, `None` until DTM was
+    /// received. Positions are moved to the reference datum of DTM when
+    /// its offsets are known.
+    pub datum: Option<Datum>,
+    dtm: Option<DtmData>,
+    satellites: Vec<Satellite>,
+    satellites_scan: BTreeMap<Option<GnssType>, Vec<Vec<Satellite>>>,
+    required_sentences_for_nav: BTreeSet<SentenceType>,
+    last_fix_time: Option<NaiveTime>,
+    sentences_for_this_time: BTreeSet<SentenceType>,
+}
+
+impl Nmea {
+    /// Constructs a new `Nmea`.
+    /// This struct parses NMEA sentences, including checksum checks and sentence
+    /// validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::new();
+    /// let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+    /// nmea.parse(gga).unwrap();
+    /// println!("{}", nmea);
+    /// ```
+    pub fn new() -> Nmea {
+        Nmea::default()
+    }
+
+    /// Constructs a new `Nmea` for navigation purposes.
+    ///
+    /// `parse_for_fix` reports a fix only once every sentence type from
+    /// `required_sentences_for_nav` was received for the same fix time.
+    pub fn create_for_navigation(required_sentences_for_nav: BTreeSet<SentenceType>)
+                                 -> core::result::Result<Nmea, &'static str> {
+        if required_sentences_for_nav.is_empty() {
+            return Err("Should be at least one sentence type in required");
+        }
+        Ok(Nmea {
+            required_sentences_for_nav,
+            ..Default::default()
+        })
+    }
+
+    /// Returns time of last fix
+    pub fn fix_timestamp(&self) -> Option<NaiveTime> {
+        self.fix_time
+    }
+
+    /// Returns fix type
+    pub fn fix_type(&self) -> Option<FixType> {
+        self.fix_type.clone()
+    }
+
+    /// Returns last fixed latitude in degrees. None if not fixed.
+    pub fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    /// Returns last fixed longitude in degrees. None if not fixed.
+    pub fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+
+    /// Returns altitude from last fix. None if not available.
+    pub fn altitude(&self) -> Option<f32> {
+        self.altitude
+    }
+
+    /// Returns the number of satellites used for fix.
+    pub fn fix_satellites(&self) -> Option<u32> {
+        self.num_of_fix_satellites
+    }
+
+    /// Returns the fix HDOP
+    pub fn hdop(&self) -> Option<f32> {
+        self.hdop
+    }
+
+    /// Returns the height of geoid above WGS84
+    pub fn geoid_height(&self) -> Option<f32> {
+        self.geoid_height
+    }
+
+    /// Returns satellites in view from the latest GSV cycle
+    pub fn satellites(&self) -> Vec<Satellite> {
+        self.satellites.clone()
+    }
+
+    fn merge_gga_data(&mut self, mut gga_data: GgaData) {
+        if let Some(ref dtm) = self.dtm {
+            self.datum = Some(dtm.correct_gga(&mut gga_data));
+        }
+        self.fix_time = gga_data.fix_time;
+        self.latitude = gga_data.latitude;
+        self.longitude = gga_data.longitude;
+        self.fix_type = gga_data.fix_type;
+        self.num_of_fix_satellites = gga_data.fix_satellites;
+        self.hdop = gga_data.hdop;
+        self.altitude = gga_data.altitude;
+        self.geoid_height = gga_data.geoid_height;
+    }
+
+    fn merge_gsv_data(&mut self, data: GsvData) -> Result<(), ParseError> {
+        if data.sentence_num == 0 || data.sentence_num > data.number_of_sentences {
+            return Err(ParseError::InvalidSentenceNum);
+        }
+        {
+            let d = self.satellites_scan
+                .entry(data.gnss_type.clone())
+                .or_default();
+            // Adjust size to this scan
+            d.resize(data.number_of_sentences as usize, vec![]);
+            // Replace data at index with new scan data
+            d[data.sentence_num as usize - 1] = data.sats_info
+                .iter()
+                .filter_map(|v| v.clone())
+                .collect();
+        }
+        self.satellites.clear();
+        for v in self.satellites_scan.values() {
+            for v1 in v {
+                for v2 in v1 {
+                    self.satellites.push(v2.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_rmc_data(&mut self, mut rmc_data: RmcData) {
+        if let Some(ref dtm) = self.dtm {
+            self.datum = Some(dtm.correct_rmc(&mut rmc_data));
+        }
+        self.fix_time = rmc_data.fix_time;
+        self.fix_date = rmc_data.fix_date;
+        if self.fix_type.is_none() {
+            self.fix_type = rmc_data.status_of_fix.map(|v| match v {
+                RmcStatusOfFix::Autonomous => FixType::Gps,
+                RmcStatusOfFix::Differential => FixType::DGps,
+                RmcStatusOfFix::Invalid => FixType::Invalid,
+            });
+        }
+        self.latitude = rmc_data.lat;
+        self.longitude = rmc_data.lon;
+        self.speed_over_ground = rmc_data.speed_over_ground;
+        self.true_course = rmc_data.true_course;
+    }
+
+    fn merge_gsa_data(&mut self, gsa: GsaData) {
+        self.fix_satellites_prns = Some(gsa.fix_sats_prn);
+        self.hdop = gsa.hdop;
+        self.vdop = gsa.vdop;
+        self.pdop = gsa.pdop;
+    }
+
+    fn merge_gll_data(&mut self, mut gll: GllData) {
+        if let Some(ref dtm) = self.dtm {
+            self.datum = Some(dtm.correct_gll(&mut gll));
+        }
+        self.fix_time = gll.fix_time;
+        self.latitude = gll.latitude;
+        self.longitude = gll.longitude;
+        if self.fix_type.is_none() {
+            self.fix_type = Some(match (gll.valid, gll.faa_mode) {
+                (false, _) | (_, Some(FaaMode::NotValid)) => FixType::Invalid,
+                (true, Some(FaaMode::Differential)) => FixType::DGps,
+                (true, Some(FaaMode::Estimated)) => FixType::Estimated,
+                (true, Some(FaaMode::Manual)) => FixType::Manual,
+                (true, Some(FaaMode::Simulator)) => FixType::Simulation,
+                (true, _) => FixType::Gps,
+            });
+        }
+    }
+
+    fn merge_gns_data(&mut self, mut gns: GnsData) {
+        if let Some(ref dtm) = self.dtm {
+            self.datum = Some(dtm.correct_gns(&mut gns));
+        }
+        self.fix_time = gns.fix_time;
+        self.latitude = gns.latitude;
+        self.longitude = gns.longitude;
+        self.fix_type = Some(gns.fix_type());
+        self.num_of_fix_satellites = gns.fix_satellites;
+        self.hdop = gns.hdop;
+        self.altitude = gns.altitude;
+        self.geoid_height = gns.geoid_height;
+    }
+
+    fn merge_vtg_data(&mut self, vtg: VtgData) {
+        self.speed_over_ground = vtg.speed_over_ground;
+        self.true_course = vtg.true_course;
+    }
+
+    /// Parse any NMEA sentence and stores the result. The type of sentence
+    /// is returned if implemented and valid.
+    pub fn parse(&mut self, s: &str) -> Result<SentenceType, ParseError> {
+        match parse(s.as_bytes())? {
+            ParseResult::VTG(vtg) => {
+                self.merge_vtg_data(vtg);
+                Ok(SentenceType::VTG)
+            }
+            ParseResult::GGA(gga) => {
+                self.merge_gga_data(gga);
+                Ok(SentenceType::GGA)
+            }
+            ParseResult::RMC(rmc) => {
+                self.merge_rmc_data(rmc);
+                Ok(SentenceType::RMC)
+            }
+            ParseResult::GSA(gsa) => {
+                self.merge_gsa_data(gsa);
+                Ok(SentenceType::GSA)
+            }
+            ParseResult::GSV(gsv) => {
+                self.merge_gsv_data(gsv)?;
+                Ok(SentenceType::GSV)
+            }
+            ParseResult::GLL(gll) => {
+                self.merge_gll_data(gll);
+                Ok(SentenceType::GLL)
+            }
+            ParseResult::ZDA(_) => Ok(SentenceType::ZDA),
+            ParseResult::GST(_) => Ok(SentenceType::GST),
+            ParseResult::GNS(gns) => {
+                self.merge_gns_data(gns);
+                Ok(SentenceType::GNS)
+            }
+            ParseResult::GBS(_) => Ok(SentenceType::GBS),
+            ParseResult::HDT(_) => Ok(SentenceType::HDT),
+            ParseResult::HDM(_) => Ok(SentenceType::HDM),
+            ParseResult::HDG(_) => Ok(SentenceType::HDG),
+            ParseResult::THS(_) => Ok(SentenceType::THS),
+            ParseResult::MWV(_) => Ok(SentenceType::MWV),
+            ParseResult::MWD(_) => Ok(SentenceType::MWD),
+            ParseResult::VWR(_) => Ok(SentenceType::VWR),
+            ParseResult::DBT(_) => Ok(SentenceType::DBT),
+            ParseResult::DBS(_) => Ok(SentenceType::DBS),
+            ParseResult::DPT(_) => Ok(SentenceType::DPT),
+            ParseResult::VHW(_) => Ok(SentenceType::VHW),
+            ParseResult::VBW(_) => Ok(SentenceType::VBW),
+            ParseResult::VLW(_) => Ok(SentenceType::VLW),
+            ParseResult::XTE(_) => Ok(SentenceType::XTE),
+            ParseResult::APB(_) => Ok(SentenceType::APB),
+            ParseResult::RMB(_) => Ok(SentenceType::RMB),
+            ParseResult::BWC(_) => Ok(SentenceType::BWC),
+            ParseResult::BOD(_) => Ok(SentenceType::BOD),
+            ParseResult::WPL(_) => Ok(SentenceType::WPL),
+            ParseResult::RTE(_) => Ok(SentenceType::RTE),
+            ParseResult::VDM(_) => Ok(SentenceType::VDM),
+            ParseResult::VDO(_) => Ok(SentenceType::VDO),
+            ParseResult::TXT(_) => Ok(SentenceType::TXT),
+            ParseResult::DTM(dtm) => {
+                self.dtm = Some(dtm);
+                Ok(SentenceType::DTM)
+            }
+            ParseResult::GRS(_) => Ok(SentenceType::GRS),
+            ParseResult::ROT(_) => Ok(SentenceType::ROT),
+            ParseResult::RSA(_) => Ok(SentenceType::RSA),
+            ParseResult::XDR(_) => Ok(SentenceType::XDR),
+            ParseResult::MTW(_) => Ok(SentenceType::MTW),
+            ParseResult::MDA(_) => Ok(SentenceType::MDA),
+            ParseResult::TTM(_) => Ok(SentenceType::TTM),
+            ParseResult::TLL(_) => Ok(SentenceType::TLL),
+            ParseResult::OSD(_) => Ok(SentenceType::OSD),
+            ParseResult::Unsupported(_) => Ok(SentenceType::None),
+        }
+    }
+
+    fn new_tick(&mut self) {
+        let old = mem::take(self);
+        self.satellites_scan = old.satellites_scan;
+        self.satellites = old.satellites;
+        self.dtm = old.dtm;
+        self.datum = old.datum;
+        self.required_sentences_for_nav = old.required_sentences_for_nav;
+        self.last_fix_time = old.last_fix_time;
+    }
+
+    fn clear_position_info(&mut self) {
+        self.last_fix_time = None;
+        self.new_tick();
+    }
+
+    /// Starts a new epoch if `fix_time` differs from the previous one.
+    /// Returns `false` if the sentence carries no time, so position info
+    /// can't be attributed to any epoch.
+    fn check_fix_time(&mut self, fix_time: Option<NaiveTime>) -> bool {
+        match (self.last_fix_time, fix_time) {
+            (Some(ref last_fix_time), Some(ref fix_time)) => {
+                if *last_fix_time != *fix_time {
+                    self.new_tick();
+                    self.last_fix_time = Some(*fix_time);
+                }
+                true
+            }
+            (None, Some(ref fix_time)) => {
+                self.last_fix_time = Some(*fix_time);
+                true
+            }
+            (Some(_), None) | (None, None) => {
+                self.clear_position_info();
+                false
+            }
+        }
+    }
+
+    /// Parse NMEA sentence and merge it into the current epoch.
+    ///
+    /// Returns the fix type once all sentences required for navigation
+    /// (see `create_for_navigation`) were received for one fix time,
+    /// `FixType::Invalid` otherwise.
+    pub fn parse_for_fix(&mut self, xs: &[u8]) -> Result<FixType, ParseError> {
+        match parse(xs)? {
+            ParseResult::GSA(gsa) => {
+                self.merge_gsa_data(gsa);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::VTG(vtg) => {
+                //have no time field, so only if user explicity mention it
+                if self.required_sentences_for_nav.contains(&SentenceType::VTG) {
+                    if vtg.true_course.is_none() || vtg.speed_over_ground.is_none() {
+                        self.clear_position_info();
+                        return Ok(FixType::Invalid);
+                    }
+                    self.merge_vtg_data(vtg);
+                    self.sentences_for_this_time.insert(SentenceType::VTG);
+                } else {
+                    return Ok(FixType::Invalid);
+                }
+            }
+            ParseResult::RMC(rmc) => {
+                if rmc.status_of_fix == Some(RmcStatusOfFix::Invalid) {
+                    self.clear_position_info();
+                    return Ok(FixType::Invalid);
+                }
+                if !self.check_fix_time(rmc.fix_time) {
+                    return Ok(FixType::Invalid);
+                }
+                self.merge_rmc_data(rmc);
+                self.sentences_for_this_time.insert(SentenceType::RMC);
+            }
+            ParseResult::GGA(gga) => {
+                match gga.fix_type {
+                    Some(FixType::Invalid) | None => {
+                        self.clear_position_info();
+                        return Ok(FixType::Invalid);
+                    }
+                    _ => {}
+                }
+                if !self.check_fix_time(gga.fix_time) {
+                    return Ok(FixType::Invalid);
+                }
+                self.merge_gga_data(gga);
+                self.sentences_for_this_time.insert(SentenceType::GGA);
+            }
+            ParseResult::GNS(gns) => {
+                if gns.fix_type() == FixType::Invalid {
+                    self.clear_position_info();
+                    return Ok(FixType::Invalid);
+                }
+                if !self.check_fix_time(gns.fix_time) {
+                    return Ok(FixType::Invalid);
+                }
+                self.merge_gns_data(gns);
+                self.sentences_for_this_time.insert(SentenceType::GNS);
+            }
+            ParseResult::GLL(gll) => {
+                if !gll.valid || gll.faa_mode == Some(FaaMode::NotValid) {
+                    self.clear_position_info();
+                    return Ok(FixType::Invalid);
+                }
+                if !self.check_fix_time(gll.fix_time) {
+                    return Ok(FixType::Invalid);
+                }
+                self.merge_gll_data(gll);
+                self.sentences_for_this_time.insert(SentenceType::GLL);
+            }
+            ParseResult::GSV(gsv) => {
+                self.merge_gsv_data(gsv)?;
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DTM(dtm) => {
+                self.dtm = Some(dtm);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::ZDA(_) | ParseResult::GST(_) | ParseResult::GBS(_) |
+            ParseResult::HDT(_) | ParseResult::HDM(_) | ParseResult::HDG(_) |
+            ParseResult::THS(_) | ParseResult::MWV(_) | ParseResult::MWD(_) |
+            ParseResult::VWR(_) | ParseResult::DBT(_) | ParseResult::DBS(_) |
+            ParseResult::DPT(_) | ParseResult::VHW(_) | ParseResult::VBW(_) |
+            ParseResult::VLW(_) | ParseResult::XTE(_) | ParseResult::APB(_) |
+            ParseResult::RMB(_) | ParseResult::BWC(_) | ParseResult::BOD(_) |
+            ParseResult::WPL(_) | ParseResult::RTE(_) | ParseResult::VDM(_) |
+            ParseResult::VDO(_) | ParseResult::TXT(_) | ParseResult::GRS(_) |
+            ParseResult::ROT(_) | ParseResult::RSA(_) | ParseResult::XDR(_) |
+            ParseResult::MTW(_) | ParseResult::MDA(_) | ParseResult::TTM(_) |
+            ParseResult::TLL(_) | ParseResult::OSD(_) | ParseResult::Unsupported(_) => {
+                return Ok(FixType::Invalid);
+            }
+        }
+        match self.fix_type {
+            Some(FixType::Invalid) | None => Ok(FixType::Invalid),
+            Some(ref fix_type) if self.required_sentences_for_nav
+                                      .is_subset(&self.sentences_for_this_time) => {
+                Ok(fix_type.clone())
+            }
+            _ => Ok(FixType::Invalid),
+        }
+    }
+}
 
-pub use parse::{GsvData, GgaData, RmcData, RmcStatusOfFix, parse, ParseResult, GsaData, VtgData};
+impl fmt::Display for Nmea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{}: lat: {} lon: {} alt: {} {:?}",
+               self.fix_time
+                   .map(|l| format!("{:?}", l))
+                   .unwrap_or("None".to_owned()),
+               self.latitude
+                   .map(|l| format!("{:3.8} {}", l.abs(), if l.is_sign_negative() { 'S' } else { 'N' }))
+                   .unwrap_or("None".to_owned()),
+               self.longitude
+                   .map(|l| format!("{:3.8} {}", l.abs(), if l.is_sign_negative() { 'W' } else { 'E' }))
+                   .unwrap_or("None".to_owned()),
+               self.altitude
+                   .map(|l| format!("{:.3}", l))
+                   .unwrap_or("None".to_owned()),
+               self.satellites)
+    }
+}
 
 #[derive(Clone, PartialEq)]
 /// ! A Satellite
@@ -50,6 +537,7 @@ pub struct Satellite {
     elevation: Option<f32>,
     azimuth: Option<f32>,
     snr: Option<f32>,
+    used: bool,
 }
 
 impl Satellite {
@@ -72,6 +560,11 @@ impl Satellite {
     pub fn snr(&self) -> Option<f32> {
         self.snr
     }
+
+    /// Whether the satellite takes part in the fix, see `GsvAssembler::update_used`
+    pub fn used(&self) -> bool {
+        self.used
+    }
 }
 
 impl fmt::Display for Satellite {
@@ -95,12 +588,13 @@ impl fmt::Display for Satellite {
 impl fmt::Debug for Satellite {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f,
-               "[{:?},{:?},{:?},{:?},{:?}]",
+               "[{:?},{:?},{:?},{:?},{:?},{:?}]",
                self.gnss_type,
                self.prn,
                self.elevation,
                self.azimuth,
-               self.snr)
+               self.snr,
+               self.used)
     }
 }
 /// ! Fix type
@@ -118,11 +612,47 @@ pub enum FixType {
 }
 
 /// ! GNSS type
-#[derive (Debug, Clone, Hash, Eq, PartialEq)]
+#[derive (Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub enum GnssType {
     Galileo,
     Gps,
     Glonass,
+    Beidou,
+    Qzss,
+    Navic,
+    Sbas,
+}
+
+impl GnssType {
+    /// Constellation implied by the talker id. `None` for GN (any combination
+    /// of GNSS) and unknown talkers.
+    ///
+    /// GP talker is also used for SBAS and QZSS satellites, see
+    /// `prn::identify`.
+    pub fn from_talker_id(talker_id: &[u8]) -> Option<GnssType> {
+        match talker_id {
+            b"GP" => Some(GnssType::Gps),
+            b"GL" => Some(GnssType::Glonass),
+            b"GA" => Some(GnssType::Galileo),
+            b"BD" | b"GB" => Some(GnssType::Beidou),
+            b"QZ" => Some(GnssType::Qzss),
+            b"GI" => Some(GnssType::Navic),
+            _ => None,
+        }
+    }
+
+    /// Constellation from the GNSS system ID field, NMEA 4.10 and later
+    pub fn from_system_id(system_id: u8) -> Option<GnssType> {
+        match system_id {
+            1 => Some(GnssType::Gps),
+            2 => Some(GnssType::Glonass),
+            3 => Some(GnssType::Galileo),
+            4 => Some(GnssType::Beidou),
+            5 => Some(GnssType::Qzss),
+            6 => Some(GnssType::Navic),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for GnssType {
@@ -131,7 +661,141 @@ impl fmt::Display for GnssType {
             GnssType::Galileo => write!(f, "Galileo"),
             GnssType::Gps => write!(f, "GPS"),
             GnssType::Glonass => write!(f, "GLONASS"),
+            GnssType::Beidou => write!(f, "BeiDou"),
+            GnssType::Qzss => write!(f, "QZSS"),
+            GnssType::Navic => write!(f, "NavIC"),
+            GnssType::Sbas => write!(f, "SBAS"),
+        }
+    }
+}
+
+/// ! Signal ID, NMEA 4.10 and later
+///
+/// Meaning of the signal ID field depends on the constellation,
+/// 0 always means all signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    All,
+    GpsL1Ca,
+    GpsL1PY,
+    GpsL1M,
+    GpsL2PY,
+    GpsL2CM,
+    GpsL2CL,
+    GpsL5I,
+    GpsL5Q,
+    GlonassG1Ca,
+    GlonassG1P,
+    GlonassG2Ca,
+    GlonassG2P,
+    GalileoE5a,
+    GalileoE5b,
+    GalileoE5ab,
+    GalileoE6A,
+    GalileoE6BC,
+    GalileoL1A,
+    GalileoL1BC,
+    BeidouB1I,
+    BeidouB1Q,
+    BeidouB1C,
+    BeidouB1A,
+    BeidouB2a,
+    BeidouB2b,
+    BeidouB2ab,
+    BeidouB3I,
+    BeidouB3Q,
+    BeidouB3A,
+    BeidouB2I,
+    BeidouB2Q,
+    QzssL1Ca,
+    QzssL1CD,
+    QzssL1CP,
+    QzssLIS,
+    QzssL2CM,
+    QzssL2CL,
+    QzssL5I,
+    QzssL5Q,
+    QzssL6D,
+    QzssL6E,
+    NavicL5Sps,
+    NavicSSps,
+    NavicL5Rs,
+    NavicSRs,
+    NavicL1Sps,
+}
+
+impl Signal {
+    /// Decode NMEA signal ID of the given constellation
+    pub fn from_nmea(gnss_type: &GnssType, id: u8) -> Option<Signal> {
+        use Signal::*;
+        if id == 0 {
+            return Some(All);
         }
+        let signals: &[Signal] = match *gnss_type {
+            GnssType::Gps | GnssType::Sbas => &[GpsL1Ca, GpsL1PY, GpsL1M, GpsL2PY, GpsL2CM,
+                                                GpsL2CL, GpsL5I, GpsL5Q],
+            GnssType::Glonass => &[GlonassG1Ca, GlonassG1P, GlonassG2Ca, GlonassG2P],
+            GnssType::Galileo => &[GalileoE5a, GalileoE5b, GalileoE5ab, GalileoE6A,
+                                   GalileoE6BC, GalileoL1A, GalileoL1BC],
+            GnssType::Beidou => &[BeidouB1I, BeidouB1Q, BeidouB1C, BeidouB1A, BeidouB2a,
+                                  BeidouB2b, BeidouB2ab, BeidouB3I, BeidouB3Q, BeidouB3A,
+                                  BeidouB2I, BeidouB2Q],
+            GnssType::Qzss => &[QzssL1Ca, QzssL1CD, QzssL1CP, QzssLIS, QzssL2CM, QzssL2CL,
+                                QzssL5I, QzssL5Q, QzssL6D, QzssL6E],
+            GnssType::Navic => &[NavicL5Sps, NavicSSps, NavicL5Rs, NavicSRs, NavicL1Sps],
+        };
+        signals.get(id as usize - 1).cloned()
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Signal::*;
+        let name = match *self {
+            All => "all signals",
+            GpsL1Ca | QzssL1Ca => "L1 C/A",
+            GpsL1PY => "L1 P(Y)",
+            GpsL1M => "L1 M",
+            GpsL2PY => "L2 P(Y)",
+            GpsL2CM | QzssL2CM => "L2C-M",
+            GpsL2CL | QzssL2CL => "L2C-L",
+            GpsL5I | QzssL5I => "L5-I",
+            GpsL5Q | QzssL5Q => "L5-Q",
+            GlonassG1Ca => "G1 C/A",
+            GlonassG1P => "G1 P",
+            GlonassG2Ca => "G2 C/A",
+            GlonassG2P => "G2 P",
+            GalileoE5a => "E5a",
+            GalileoE5b => "E5b",
+            GalileoE5ab => "E5 a+b",
+            GalileoE6A => "E6-A",
+            GalileoE6BC => "E6-BC",
+            GalileoL1A => "L1-A",
+            GalileoL1BC => "L1-BC",
+            BeidouB1I => "B1I",
+            BeidouB1Q => "B1Q",
+            BeidouB1C => "B1C",
+            BeidouB1A => "B1A",
+            BeidouB2a => "B2-a",
+            BeidouB2b => "B2-b",
+            BeidouB2ab => "B2 a+b",
+            BeidouB3I => "B3I",
+            BeidouB3Q => "B3Q",
+            BeidouB3A => "B3A",
+            BeidouB2I => "B2I",
+            BeidouB2Q => "B2Q",
+            QzssL1CD => "L1C(D)",
+            QzssL1CP => "L1C(P)",
+            QzssLIS => "LIS",
+            QzssL6D => "L6D",
+            QzssL6E => "L6E",
+            NavicL5Sps => "L5-SPS",
+            NavicSSps => "S-SPS",
+            NavicL5Rs => "L5-RS",
+            NavicSRs => "S-RS",
+            NavicL1Sps => "L1-SPS",
+        };
+        f.write_str(name)
     }
 }
 
@@ -152,3 +816,128 @@ impl From<char> for FixType {
     }
 }
 
+
+macro_rules! define_sentence_type_enum {
+    ($Name:ident { $($Variant:ident),* $(,)* }) => {
+        /// ! NMEA sentence type
+        #[derive(PartialEq, Debug, Hash, Eq, Clone, PartialOrd, Ord)]
+        pub enum $Name {
+            None,
+            $($Variant),*,
+        }
+
+        impl $Name {
+            /// Map three letter message id (without talker id) to sentence type,
+            /// `None` for unknown message ids
+            pub fn try_from(s: &[u8]) -> Result<Self, ParseError> {
+                match core::str::from_utf8(s).map_err(|_| ParseError::InvalidMessageId)? {
+                    $(stringify!($Variant) => Ok($Name::$Variant),)*
+                    _ => Ok($Name::None),
+                }
+            }
+        }
+    }
+}
+
+define_sentence_type_enum!(SentenceType {
+    AAM,
+    ABK,
+    ACA,
+    ACK,
+    ACS,
+    AIR,
+    ALM,
+    ALR,
+    APA,
+    APB,
+    ASD,
+    BEC,
+    BOD,
+    BWC,
+    BWR,
+    BWW,
+    CUR,
+    DBK,
+    DBS,
+    DBT,
+    DCN,
+    DPT,
+    DSC,
+    DSE,
+    DSI,
+    DSR,
+    DTM,
+    FSI,
+    GBS,
+    GGA,
+    GLC,
+    GLL,
+    GMP,
+    GNS,
+    GRS,
+    GSA,
+    GST,
+    GSV,
+    GTD,
+    GXA,
+    HDG,
+    HDM,
+    HDT,
+    HMR,
+    HMS,
+    HSC,
+    HTC,
+    HTD,
+    LCD,
+    LRF,
+    LRI,
+    LR1,
+    LR2,
+    LR3,
+    MDA,
+    MLA,
+    MSK,
+    MSS,
+    MTW,
+    MWD,
+    MWV,
+    OSD,
+    RMA,
+    RMB,
+    RMC,
+    ROT,
+    RPM,
+    RSA,
+    RSD,
+    RTE,
+    SFI,
+    SSD,
+    STN,
+    THS,
+    TLB,
+    TLL,
+    TRF,
+    TTM,
+    TUT,
+    TXT,
+    VBW,
+    VDM,
+    VDO,
+    VDR,
+    VHW,
+    VLW,
+    VPW,
+    VSD,
+    VTG,
+    VWR,
+    WCV,
+    WNC,
+    WPL,
+    XDR,
+    XTE,
+    XTR,
+    ZDA,
+    ZDL,
+    ZFO,
+    ZTG,
+});
@@ -0,0 +1,149 @@
+//! Sequencing of multi-sentence messages.
+//!
+//! GSV, RTE, TXT and VDM split one message into numbered sentences,
+//! pages, that have to arrive in order. Pages of different messages may
+//! be interleaved, so every message has its own cycle, keyed by whatever
+//! tells them apart: talker, route ID, text ID and so on.
+
+use alloc::collections::BTreeMap;
+
+/// Result of feeding one page into `PageSequence`
+#[derive(Debug, PartialEq)]
+pub enum PageStatus {
+    /// Page stored, more pages of the cycle are expected
+    Pending,
+    /// Page 1 arrived before the previous cycle was finished,
+    /// pages of the previous cycle were dropped
+    Restarted { dropped_pages: u16 },
+    /// Last page of the cycle arrived. `dropped_pages` is nonzero if the
+    /// cycle has only one page and it dropped an unfinished previous cycle.
+    Complete { dropped_pages: u16 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PageError {
+    /// Sentence number is zero or greater than number of sentences
+    InvalidSentenceNum,
+    /// The page is the same as the previous one, it was ignored
+    DuplicatePage(u16),
+    /// The page belongs to the current cycle but was already received,
+    /// it was ignored
+    OutOfOrder { expected: u16, received: u16 },
+    /// Pages between `expected` and `received` never arrived,
+    /// current cycle was dropped
+    MissingPage { expected: u16, received: u16 },
+    /// Number of sentences changed in the middle of the cycle,
+    /// current cycle was dropped
+    PageCountChanged { expected: u16, received: u16 },
+}
+
+struct Cycle<T> {
+    number_of_sentences: u16,
+    next_page: u16,
+    data: T,
+}
+
+/// Keeps track of pages of interleaved multi-sentence cycles, keyed by `K`,
+/// and gathers their contents into `T`
+pub struct PageSequence<K, T> {
+    cycles: BTreeMap<K, Cycle<T>>,
+}
+
+impl<K: Ord, T> Default for PageSequence<K, T> {
+    fn default() -> PageSequence<K, T> {
+        PageSequence { cycles: BTreeMap::new() }
+    }
+}
+
+impl<K: Ord + Clone, T> PageSequence<K, T> {
+    pub fn new() -> PageSequence<K, T> {
+        PageSequence::default()
+    }
+
+    /// Feed page `sentence_num` of `number_of_sentences` of the cycle `key`.
+    ///
+    /// Page 1 starts a new cycle with contents from `start`. Every accepted
+    /// page is merged into the contents by `extend`; if it fails, the cycle
+    /// is dropped. Returns the contents of the cycle with its last page.
+    pub fn add<E, S, F>(&mut self,
+                        key: K,
+                        sentence_num: u16,
+                        number_of_sentences: u16,
+                        start: S,
+                        extend: F)
+                        -> Result<(PageStatus, Option<T>), E>
+        where E: From<PageError>,
+              S: FnOnce() -> T,
+              F: FnOnce(&mut T) -> Result<(), E>
+    {
+        if sentence_num == 0 || sentence_num > number_of_sentences {
+            return Err(PageError::InvalidSentenceNum.into());
+        }
+        let mut dropped_pages = 0;
+        if sentence_num == 1 {
+            if let Some(old) = self.cycles.remove(&key) {
+                dropped_pages = old.next_page - 1;
+            }
+            self.cycles.insert(key.clone(),
+                               Cycle {
+                                   number_of_sentences,
+                                   next_page: 1,
+                                   data: start(),
+                               });
+        }
+
+        let result = {
+            let cycle = match self.cycles.get_mut(&key) {
+                Some(cycle) => cycle,
+                None => {
+                    return Err(PageError::MissingPage {
+                                       expected: 1,
+                                       received: sentence_num,
+                                   }
+                                   .into())
+                }
+            };
+            if sentence_num < cycle.next_page {
+                return Err(if sentence_num + 1 == cycle.next_page {
+                                   PageError::DuplicatePage(sentence_num)
+                               } else {
+                                   PageError::OutOfOrder {
+                                       expected: cycle.next_page,
+                                       received: sentence_num,
+                                   }
+                               }
+                               .into());
+            }
+            if number_of_sentences != cycle.number_of_sentences {
+                Err(PageError::PageCountChanged {
+                            expected: cycle.number_of_sentences,
+                            received: number_of_sentences,
+                        }
+                        .into())
+            } else if sentence_num > cycle.next_page {
+                Err(PageError::MissingPage {
+                            expected: cycle.next_page,
+                            received: sentence_num,
+                        }
+                        .into())
+            } else {
+                extend(&mut cycle.data).map(|_| {
+                                                cycle.next_page += 1;
+                                                cycle.next_page > cycle.number_of_sentences
+                                            })
+            }
+        };
+        match result {
+            Ok(false) if dropped_pages > 0 => Ok((PageStatus::Restarted { dropped_pages }, None)),
+            Ok(false) => Ok((PageStatus::Pending, None)),
+            Ok(true) => {
+                let cycle = self.cycles.remove(&key).unwrap();
+                Ok((PageStatus::Complete { dropped_pages }, Some(cycle.data)))
+            }
+            Err(err) => {
+                self.cycles.remove(&key);
+                Err(err)
+            }
+        }
+    }
+}
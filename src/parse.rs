@@ -1,12 +1,15 @@
-use core::str;
+use core::{fmt, str};
+use alloc::string::String;
 use alloc::vec::Vec;
 
-use time::{NaiveDate, NaiveTime};
+use time::{NaiveDate, NaiveTime, NaiveDateTime};
 use nom;
 use nom::{digit, IResult, AsChar, Err};
 
 use GnssType;
 use Satellite;
+use Signal;
+use prn;
 use FixType;
 
 pub type Result<T> = core::result::Result<T, ParseError>;
@@ -23,8 +26,29 @@ pub enum ParseError {
     NumberFail,
     InvalidTime,
     InvalidDate,
-    InvalidFixStatus
+    InvalidFixStatus,
+    InvalidSentenceNum,
 }
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            ParseError::TooLongMessage => "sentence is too long",
+            ParseError::Incomplete => "incomplete sentence",
+            ParseError::Nom => "malformed sentence",
+            ParseError::UnknownGnss => "unknown GNSS type",
+            ParseError::InvalidMessageId => "invalid message id",
+            ParseError::ChecksumFail => "checksum mismatch",
+            ParseError::NumberFail => "invalid number",
+            ParseError::InvalidTime => "invalid time",
+            ParseError::InvalidDate => "invalid date",
+            ParseError::InvalidFixStatus => "invalid fix status",
+            ParseError::InvalidSentenceNum => "invalid sentence number",
+        };
+        f.write_str(msg)
+    }
+}
+
 pub struct NmeaSentence<'a> {
     pub talker_id: &'a [u8],
     pub message_id: &'a [u8],
@@ -37,17 +61,33 @@ impl<'a> NmeaSentence<'a> {
         checksum(self.talker_id
                      .iter()
                      .chain(self.message_id.iter())
-                     .chain(&[b','])
+                     .chain(b",")
                      .chain(self.data.iter()))
     }
 }
 
+#[derive(Debug)]
 pub struct GsvData {
-    pub gnss_type: GnssType,
+    /// Constellation from talker id, `None` for GN talker, whose cycle may
+    /// mix constellations. See `gnss_type` of each satellite.
+    pub gnss_type: Option<GnssType>,
     pub number_of_sentences: u16,
     pub sentence_num: u16,
     pub _sats_in_view: u16,
     pub sats_info: [Option<Satellite>; 4],
+    /// Signal ID, NMEA 4.10 and later
+    pub signal_id: Option<u8>,
+}
+
+impl GsvData {
+    /// Decoded signal ID, see `Signal::from_nmea`. For GN talker
+    /// the constellation of the first satellite is used.
+    pub fn signal(&self) -> Option<Signal> {
+        let gnss_type = self.gnss_type
+            .clone()
+            .or_else(|| self.sats_info.iter().flatten().map(|v| v.gnss_type.clone()).next())?;
+        self.signal_id.and_then(|id| Signal::from_nmea(&gnss_type, id))
+    }
 }
 
 pub fn checksum<'a, I: Iterator<Item = &'a u8>>(bytes: I) -> u8 {
@@ -78,7 +118,7 @@ named!(parse_checksum<u8>, map_res!(
 named!(do_parse_nmea_sentence<NmeaSentence>,
        map_res!(
            do_parse!(
-               char!('$') >>
+               one_of!("$!") >>
                talker_id: take!(2) >>
                message_id: take!(3) >>
                char!(',') >>
@@ -88,7 +128,7 @@ named!(do_parse_nmea_sentence<NmeaSentence>,
        )
 );
 
-pub fn parse_nmea_sentence(sentence: &[u8]) -> Result<NmeaSentence> {
+pub fn parse_nmea_sentence(sentence: &[u8]) -> Result<NmeaSentence<'_>> {
     /*
      * From gpsd:
      * We've had reports that on the Garmin GPS-10 the device sometimes
@@ -114,6 +154,10 @@ pub fn parse_nmea_sentence(sentence: &[u8]) -> Result<NmeaSentence> {
                      Err::Incomplete(_) => ParseError::Incomplete,
                      _ => ParseError::Nom,
                  })?;
+    // `!` starts encapsulated sentences, only AIS ones are known
+    if sentence[0] == b'!' && res.message_id != b"VDM" && res.message_id != b"VDO" {
+        Err(ParseError::InvalidMessageId)?
+    }
     Ok(res)
 }
 
@@ -121,65 +165,38 @@ fn parse_num<I: core::str::FromStr>(data: &[u8]) -> Result<I> {
     str::parse::<I>(unsafe { str::from_utf8_unchecked(data) }).map_err(|_| ParseError::NumberFail)
 }
 
-fn construct_satellite(data: (u32, Option<i32>, Option<i32>, Option<i32>))
-                       -> Result<Satellite> {
-    Ok(Satellite {
-           gnss_type: GnssType::Galileo,
-           prn: data.0,
-           elevation: data.1.map(|v| v as f32),
-           azimuth: data.2.map(|v| v as f32),
-           snr: data.3.map(|v| v as f32),
-       })
-}
-
-named!(parse_gsv_sat_info<Satellite>,
-       map_res!(
-           do_parse!(
-               prn: map_res!(digit, parse_num::<u32>) >>
-               char!(',') >>
-               elevation:  opt!(map_res!(digit, parse_num::<i32>)) >>
-               char!(',') >>
-               azimuth: opt!(map_res!(digit, parse_num::<i32>)) >>
-               char!(',') >>
-               signal_noise: opt!(map_res!(complete!(digit), parse_num::<i32>)) >>
-               alt!(eof!() | tag!(",")) >>
-               (prn, elevation, azimuth, signal_noise)),
-           construct_satellite
-       ));
+/// PRN, elevation, azimuth and SNR of one satellite in GSV
+type GsvSatInfo = (u32, Option<f32>, Option<f32>, Option<f32>);
 
+named!(parse_gsv_sat_info<GsvSatInfo>,
+       do_parse!(
+           prn: map_res!(digit, parse_num::<u32>) >>
+           char!(',') >>
+           elevation:  opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           azimuth: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           signal_noise: opt!(map_res!(complete!(float_number), parse_float_num::<f32>)) >>
+           opt!(complete!(char!(','))) >>
+           (prn, elevation, azimuth, signal_noise)));
 
-fn construct_gsv_data(data: (u16,
-                             u16,
-                             u16,
-                             Option<Satellite>,
-                             Option<Satellite>,
-                             Option<Satellite>,
-                             Option<Satellite>))
-                      -> Result<GsvData> {
-    Ok(GsvData {
-           gnss_type: GnssType::Galileo,
-           number_of_sentences: data.0,
-           sentence_num: data.1,
-           _sats_in_view: data.2,
-           sats_info: [data.3, data.4, data.5, data.6],
-       })
-}
+type GsvFields = (u16, u16, u16, [Option<GsvSatInfo>; 4], Option<u8>);
 
-named!(do_parse_gsv<GsvData>,
-       map_res!(
-           do_parse!(
-               number_of_sentences: map_res!(digit, parse_num::<u16>) >>
-               char!(',') >>
-               sentence_index: map_res!(digit, parse_num::<u16>) >>
-               char!(',') >>
-               total_number_of_sats: map_res!(digit, parse_num::<u16>) >>
-               char!(',') >>
-               sat0: opt!(complete!(parse_gsv_sat_info)) >>
-               sat1: opt!(complete!(parse_gsv_sat_info)) >>
-               sat2: opt!(complete!(parse_gsv_sat_info)) >>
-               sat3: opt!(complete!(parse_gsv_sat_info)) >>
-               (number_of_sentences, sentence_index, total_number_of_sats, sat0, sat1, sat2, sat3)),
-           construct_gsv_data));
+named!(do_parse_gsv<GsvFields>,
+       do_parse!(
+           number_of_sentences: map_res!(digit, parse_num::<u16>) >>
+           char!(',') >>
+           sentence_index: map_res!(digit, parse_num::<u16>) >>
+           char!(',') >>
+           total_number_of_sats: map_res!(digit, parse_num::<u16>) >>
+           opt!(complete!(char!(','))) >>
+           sat0: opt!(complete!(parse_gsv_sat_info)) >>
+           sat1: opt!(complete!(parse_gsv_sat_info)) >>
+           sat2: opt!(complete!(parse_gsv_sat_info)) >>
+           sat3: opt!(complete!(parse_gsv_sat_info)) >>
+           signal_id: opt!(complete!(map_res!(take!(1), parse_hex))) >>
+           (number_of_sentences, sentence_index, total_number_of_sats,
+            [sat0, sat1, sat2, sat3], signal_id)));
 
 /// Parsin one GSV sentence
 /// from gpsd/driver_nmea0183.c:
@@ -200,31 +217,62 @@ named!(do_parse_gsv<GsvData>,
 ///   GL (GLONASS),
 ///   GN (GLONASS, any combination GNSS),
 ///   GP (GPS, SBAS, QZSS),
-///   QZ (QZSS).
+///   QZ (QZSS),
+///   GI (NavIC).
 ///
 /// GL may be (incorrectly) used when GSVs are mixed containing
 /// GLONASS, GN may be (incorrectly) used when GSVs contain GLONASS
-/// only.  Usage is inconsistent.
+/// only.  Usage is inconsistent. The constellation of each satellite
+/// is identified by `prn::identify`, so SBAS and QZSS satellites reported
+/// with GP talker get their own `gnss_type`. GN sentences have no
+/// `gnss_type` of their own, as one GNGSV cycle may list satellites of
+/// several constellations.
+///
+/// NMEA 4.10 adds signal ID after the last satellite:
+/// $GAGSV,3,1,09,02,46,103,38,03,42,063,40,05,25,127,37,08,22,064,,7*7D
+/// 7           Signal ID (L1-BC for Galileo)
+///
+/// Elevation, azimuth and SNR may be decimal numbers.
 pub fn parse_gsv(sentence: &NmeaSentence) -> Result<GsvData> {
     if sentence.message_id != b"GSV" {
         Err(ParseError::InvalidMessageId)?
     }
-    let gnss_type = match sentence.talker_id {
-        b"GP" => GnssType::Gps,
-        b"GL" => GnssType::Glonass,
-        _ => Err(ParseError::UnknownGnss)?
+    let talker_gnss_type = match sentence.talker_id {
+        b"GN" => None,
+        talker_id => Some(GnssType::from_talker_id(talker_id).ok_or(ParseError::UnknownGnss)?),
     };
-    let mut res: GsvData = do_parse_gsv(sentence.data)
-        .map(|(_, o)| o)
-        .map_err(|err| match err {
-                     Err::Incomplete(_) => ParseError::Incomplete,
-                     _ => ParseError::Nom,
-                 })?;
-    res.gnss_type = gnss_type.clone();
-    for sat in res.sats_info.iter_mut() {
-        (*sat).as_mut().map(|v| v.gnss_type = gnss_type.clone());
+    let (number_of_sentences, sentence_num, sats_in_view, sats, signal_id) =
+        do_parse_gsv(sentence.data)
+            .map(|(_, o)| o)
+            .map_err(|err| match err {
+                         Err::Incomplete(_) => ParseError::Incomplete,
+                         _ => ParseError::Nom,
+                     })?;
+    let mut sats_info: [Option<Satellite>; 4] = [None, None, None, None];
+    for (sat, info) in sats_info.iter_mut().zip(sats.iter()) {
+        *sat = info.map(|(prn, elevation, azimuth, snr)| {
+            let gnss_type = prn::identify(sentence.talker_id, None, prn)
+                .map(|v| v.0)
+                .or_else(|| talker_gnss_type.clone())
+                .unwrap_or(GnssType::Gps);
+            Satellite {
+                gnss_type,
+                prn,
+                elevation,
+                azimuth,
+                snr,
+                used: false,
+            }
+        });
     }
-    Ok(res)
+    Ok(GsvData {
+           gnss_type: talker_gnss_type,
+           number_of_sentences,
+           sentence_num,
+           _sats_in_view: sats_in_view,
+           sats_info,
+           signal_id,
+       })
 }
 
 #[derive(Debug, PartialEq)]
@@ -244,7 +292,7 @@ fn parse_float_num<T: str::FromStr>(input: &[u8]) -> Result<T> {
     str::parse::<T>(s).map_err(|_| ParseError::NumberFail)
 }
 
-named!(parse_hms<NaiveTime>,
+named!(pub(crate) parse_hms<NaiveTime>,
        map_res!(
            do_parse!(
                hour: map_res!(take!(2), parse_num::<u32>) >>
@@ -301,6 +349,9 @@ named!(parse_lat_lon<Option<(f64, f64)>>,
                     |v| -> Result<Option<(f64, f64)>> { Ok(Some(v)) }))
 );
 
+type GgaFields = (Option<NaiveTime>, Option<(f64, f64)>, char, Option<u32>, Option<f32>,
+                  Option<f32>, Option<f32>);
+
 named!(do_parse_gga<GgaData>,
        map_res!(
            do_parse!(
@@ -322,9 +373,7 @@ named!(do_parse_gga<GgaData>,
                char!(',') >>
                opt!(complete!(char!('M'))) >>
                (time, lat_lon, fix_quality, tracked_sats, hdop, altitude, geoid_height)),
-           |data: (Option<NaiveTime>, Option<(f64, f64)>, char, Option<u32>,
-                   Option<f32>, Option<f32>, Option<f32>)|
-                   -> Result<GgaData> {
+           |data: GgaFields| -> Result<GgaData> {
                Ok(GgaData {
                    fix_time: data.0,
                    fix_type: Some(FixType::from(data.2)),
@@ -393,13 +442,16 @@ named!(parse_date<NaiveDate>, map_res!(do_parse!(
         (day, month, year)),
     |data: (u8, u8, u8)| -> Result<NaiveDate> {
         let (day, month, year) = (data.0 as u32, data.1 as u32, (data.2 as i32));
-        if month < 1 || month > 12 || day < 1 || day > 31 {
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
             Err(ParseError::InvalidDate)?
         }
         Ok(NaiveDate { year, month, day })
     })
 );
 
+type RmcFields = (Option<NaiveTime>, char, Option<(f64, f64)>, Option<f32>, Option<f32>,
+                  Option<NaiveDate>);
+
 named!(do_parse_rmc<RmcData>,
        map_res!(
            do_parse!(
@@ -418,9 +470,7 @@ named!(do_parse_rmc<RmcData>,
                (time, status_of_fix, lat_lon, speed_over_ground,
                 true_course, date)
            ),
-           |data: (Option<NaiveTime>, char, Option<(f64, f64)>, Option<f32>,
-                   Option<f32>, Option<NaiveDate>)|
-                   -> Result<RmcData> {
+           |data: RmcFields| -> Result<RmcData> {
                Ok(RmcData {
                    fix_time: data.0,
                    fix_date: data.5,
@@ -470,13 +520,13 @@ pub fn parse_rmc(sentence: &NmeaSentence) -> Result<RmcData> {
 }
 
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum GsaMode1 {
     Manual,
     Automatic,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum GsaMode2 {
     NoFix,
     Fix2D,
@@ -488,12 +538,20 @@ pub struct GsaData {
     pub mode1: GsaMode1,
     pub mode2: GsaMode2,
     pub fix_sats_prn: Vec<u32>,
+    /// PRN fields in their order, `None` for empty ones. GRS residuals
+    /// follow this order.
+    pub fix_sats_slots: Vec<Option<u32>>,
     pub pdop: Option<f32>,
     pub hdop: Option<f32>,
     pub vdop: Option<f32>,
+    /// Constellation from GNSS system ID (NMEA 4.10 and later) or talker id,
+    /// `None` for GN talker without system ID
+    pub gnss_type: Option<GnssType>,
+    /// GNSS system ID, NMEA 4.10 and later
+    pub system_id: Option<u8>,
 }
 
-named!(gsa_prn_fields_parse<&[u8], Vec<Option<u32>>>, many0!(map_res!(do_parse!(
+named!(pub(crate) gsa_prn_fields_parse<&[u8], Vec<Option<u32>>>, many0!(map_res!(do_parse!(
     prn: opt!(map_res!(complete!(digit), parse_num::<u32>)) >>
     char!(',') >> (prn)),
     |prn: Option<u32>| -> Result<Option<u32>> {
@@ -501,7 +559,7 @@ named!(gsa_prn_fields_parse<&[u8], Vec<Option<u32>>>, many0!(map_res!(do_parse!(
     }
 )));
 
-type GsaTail = (Vec<Option<u32>>, Option<f32>, Option<f32>, Option<f32>);
+type GsaTail = (Vec<Option<u32>>, Option<f32>, Option<f32>, Option<f32>, Option<u8>);
 named!(do_parse_gsa_tail<GsaTail>, do_parse!(
     prns: gsa_prn_fields_parse >>
     pdop: map_res!(float_number, parse_float_num::<f32>) >>
@@ -509,19 +567,21 @@ named!(do_parse_gsa_tail<GsaTail>, do_parse!(
     hdop: map_res!(float_number, parse_float_num::<f32>) >>
     char!(',') >>
     vdop: map_res!(float_number, parse_float_num::<f32>) >>
-    (prns, Some(pdop), Some(hdop), Some(vdop)))
+    system_id: opt!(complete!(do_parse!(
+        char!(',') >>
+        id: map_res!(take!(1), parse_hex) >>
+        (id)))) >>
+    (prns, Some(pdop), Some(hdop), Some(vdop), system_id))
 );
 
-fn is_comma(x: u8) -> bool {
-    x == b','
+fn is_comma(x: &[u8]) -> bool {
+    x.iter().all(|c| *c == b',')
 }
 
-named!(do_parse_empty_gsa_tail<GsaTail>, map_res!(do_parse!(
-    take_while!(is_comma) >>
-    eof!() >>
-    ()),
-    |_ : ()| -> Result<GsaTail> {
-        Ok((Vec::new(), None, None, None))
+named!(do_parse_empty_gsa_tail<GsaTail>, map_res!(
+    verify!(nom::rest, is_comma),
+    |_| -> Result<GsaTail> {
+        Ok((Vec::new(), None, None, None, None))
     }
 ));
 
@@ -532,7 +592,7 @@ named!(do_parse_gsa<GsaData>, map_res!(do_parse!(
     char!(',') >>
     tail: alt_complete!(do_parse_empty_gsa_tail | do_parse_gsa_tail) >>
     (mode1, mode2, tail)),
-    |mut data:  (char, char, GsaTail)| -> Result<GsaData> {
+    |data:  (char, char, GsaTail)| -> Result<GsaData> {
         Ok(GsaData {
             mode1: match data.0 {
                 'M' => GsaMode1::Manual,
@@ -545,10 +605,13 @@ named!(do_parse_gsa<GsaData>, map_res!(do_parse!(
                 '3' => GsaMode2::Fix3D,
                 _ => unreachable!(),
             },
-            fix_sats_prn: (data.2).0.drain(..).filter_map(|v| v).collect(),
+            fix_sats_prn: (data.2).0.iter().flatten().cloned().collect(),
+            fix_sats_slots: (data.2).0,
             pdop: (data.2).1,
             hdop: (data.2).2,
             vdop: (data.2).3,
+            gnss_type: None,
+            system_id: (data.2).4,
         })
     }
 ));
@@ -588,21 +651,30 @@ named!(do_parse_gsa<GsaData>, map_res!(do_parse!(
 /// $GNGSA,A,3,75,86,87,,,,,,,,,,3.77,2.55,2.77*1C
 /// seems like the first is GNSS and the second GLONASS
 ///
+/// NMEA 4.10 adds GNSS system ID after VDOP, so GNGSA sentences
+/// can be told apart:
+/// $GNGSA,A,3,80,71,73,79,69,,,,,,,,1.83,1.09,1.47,2*09
+/// 1 = GPS, 2 = GLONASS, 3 = Galileo, 4 = BeiDou, 5 = QZSS, 6 = NavIC
+///
 /// One chipset called the i.Trek M3 issues GPGSA lines that look like
 /// this: "$GPGSA,A,1,,,,*32" when it has no fix.  This is broken
 /// in at least two ways: it's got the wrong number of fields, and
 /// it claims to be a valid sentence (A flag) when it isn't.
 /// Alarmingly, it's possible this error may be generic to SiRFstarIII
-fn parse_gsa(s: &NmeaSentence) -> Result<GsaData> {
+pub fn parse_gsa(s: &NmeaSentence) -> Result<GsaData> {
     if s.message_id != b"GSA" {
         Err(ParseError::InvalidMessageId)?
     }
-    let ret: GsaData = do_parse_gsa(s.data)
+    let mut ret: GsaData = do_parse_gsa(s.data)
         .map(|(_, o)| o)
         .map_err(|err| match err {
                      Err::Incomplete(_) => ParseError::Incomplete,
                      _ => ParseError::Nom,
                  })?;
+    ret.gnss_type = match ret.system_id {
+        Some(id) => GnssType::from_system_id(id),
+        None => GnssType::from_talker_id(s.talker_id),
+    };
     Ok(ret)
 }
 
@@ -612,7 +684,7 @@ pub struct VtgData {
     pub speed_over_ground: Option<f32>,
 }
 
-fn float_number(input: &[u8]) -> IResult<&[u8], &[u8]> {
+pub(crate) fn float_number(input: &[u8]) -> IResult<&[u8], &[u8]> {
     use nom::{InputLength, InputIter, Slice};
 
     let input_length = input.input_len();
@@ -631,8 +703,7 @@ fn float_number(input: &[u8]) -> IResult<&[u8], &[u8]> {
     for (idx, item) in input.iter_indices() {
         match state {
             State::BeforePoint => {
-                let item2 = item.clone();
-                if item2.as_char() == '.' {
+                if item.as_char() == '.' {
                     state = State::Point;
                 } else if !item.is_dec_digit() {
                     if idx == 0 {
@@ -719,7 +790,7 @@ named!(do_parse_vtg<VtgData>, map_res!(do_parse!(
 /// x.x,M = Track, degrees Magnetic
 /// x.x,N = Speed, knots
 /// x.x,K = Speed, Km/hr
-fn parse_vtg(s: &NmeaSentence) -> Result<VtgData> {
+pub fn parse_vtg(s: &NmeaSentence) -> Result<VtgData> {
     if s.message_id != b"VTG" {
         Err(ParseError::InvalidMessageId)?
     }
@@ -732,17 +803,3123 @@ fn parse_vtg(s: &NmeaSentence) -> Result<VtgData> {
     Ok(ret)
 }
 
+/// FAA mode indicator, NMEA 2.3 and later
+///
+/// Precise, RTK and float RTK modes are used only in GNS.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FaaMode {
+    Autonomous,
+    Differential,
+    Estimated,
+    Manual,
+    Simulator,
+    NotValid,
+    Precise,
+    RtkFixed,
+    RtkFloat,
+}
+
+fn parse_faa_mode(x: char) -> Result<FaaMode> {
+    Ok(match x {
+        'A' => FaaMode::Autonomous,
+        'D' => FaaMode::Differential,
+        'E' => FaaMode::Estimated,
+        'M' => FaaMode::Manual,
+        'S' => FaaMode::Simulator,
+        'N' => FaaMode::NotValid,
+        'P' => FaaMode::Precise,
+        'R' => FaaMode::RtkFixed,
+        'F' => FaaMode::RtkFloat,
+        _ => Err(ParseError::InvalidFixStatus)?,
+    })
+}
+
+impl From<FaaMode> for FixType {
+    fn from(x: FaaMode) -> Self {
+        match x {
+            FaaMode::Autonomous => FixType::Gps,
+            FaaMode::Differential => FixType::DGps,
+            FaaMode::Estimated => FixType::Estimated,
+            FaaMode::Manual => FixType::Manual,
+            FaaMode::Simulator => FixType::Simulation,
+            FaaMode::NotValid => FixType::Invalid,
+            FaaMode::Precise => FixType::Pps,
+            FaaMode::RtkFixed => FixType::Rtk,
+            FaaMode::RtkFloat => FixType::FloatRtk,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GllData {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub fix_time: Option<NaiveTime>,
+    /// Status: A = data valid, V = data invalid
+    pub valid: bool,
+    pub faa_mode: Option<FaaMode>,
+}
+
+type GllFields = (Option<(f64, f64)>, Option<NaiveTime>, char, Option<char>);
+
+named!(do_parse_gll<GllData>,
+       map_res!(
+           do_parse!(
+               lat_lon: parse_lat_lon >>
+               char!(',') >>
+               time: opt!(complete!(parse_hms)) >>
+               char!(',') >>
+               status: one_of!("AV") >>
+               faa_mode: opt!(complete!(preceded!(char!(','), one_of!("ADEMSN")))) >>
+               (lat_lon, time, status, faa_mode)
+           ),
+           |data: GllFields| -> Result<GllData> {
+               Ok(GllData {
+                   latitude: data.0.map(|v| v.0),
+                   longitude: data.0.map(|v| v.1),
+                   fix_time: data.1,
+                   valid: data.2 == 'A',
+                   faa_mode: match data.3 {
+                       Some(mode) => Some(parse_faa_mode(mode)?),
+                       None => None,
+                   },
+               })
+           }
+       )
+);
+
+/// Parse GLL
+/// from gpsd:
+/// GLL,4916.45,N,12311.12,W,225444,A,A*5C
+/// 1,2: 4916.46,N    Latitude 49 deg. 16.45 min. North
+/// 3,4: 12311.12,W   Longitude 123 deg. 11.12 min. West
+/// 5:   225444       Fix taken at 22:54:44 UTC
+/// 6:   A            Data valid
+/// 7:   A            Autonomous mode (NMEA 2.3 and later)
+///
+/// Mode indicator:
+/// A=autonomous, D=differential, E=Estimated,
+/// N=not valid, S=Simulator, M=Manual input mode
+///
+/// Before NMEA 2.3 there is no mode indicator:
+/// $GPGLL,4916.45,N,12311.12,W,225444,A*31
+pub fn parse_gll(sentence: &NmeaSentence) -> Result<GllData> {
+    if sentence.message_id != b"GLL" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_gll(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ZdaData {
+    pub utc_time: Option<NaiveTime>,
+    /// Date with four digit year
+    pub utc_date: Option<NaiveDate>,
+    /// Local zone hours, -13 to +13
+    pub local_zone_hours: Option<i32>,
+    /// Local zone minutes, with sign of the zone, so `-00,30` is -30
+    pub local_zone_minutes: Option<i32>,
+}
+
+impl ZdaData {
+    pub fn utc_date_time(&self) -> Option<NaiveDateTime> {
+        match (self.utc_date, self.utc_time) {
+            (Some(date), Some(time)) => Some(NaiveDateTime { date, time }),
+            _ => None,
+        }
+    }
+
+    /// Local zone offset in minutes, as sent by receiver
+    pub fn local_offset_minutes(&self) -> Option<i32> {
+        self.local_zone_hours
+            .map(|hours| hours * 60 + self.local_zone_minutes.unwrap_or(0))
+    }
+}
+
+named!(signed_number, recognize!(pair!(opt!(one_of!("+-")), float_number)));
+
+type ZdaFields = (Option<NaiveTime>, Option<u32>, Option<u32>, Option<i32>,
+                  Option<(bool, i32)>, Option<i32>);
+
+/// Signed number and whether it has minus sign, which `-00` would lose
+fn parse_signed_num(data: &[u8]) -> Result<(bool, i32)> {
+    parse_num::<i32>(data).map(|v| (data[0] == b'-', v))
+}
+
+named!(do_parse_zda<ZdaData>,
+       map_res!(
+           do_parse!(
+               time: opt!(complete!(parse_hms)) >>
+               char!(',') >>
+               day: opt!(complete!(map_res!(take!(2), parse_num::<u32>))) >>
+               char!(',') >>
+               month: opt!(complete!(map_res!(take!(2), parse_num::<u32>))) >>
+               char!(',') >>
+               year: opt!(complete!(map_res!(take!(4), parse_num::<i32>))) >>
+               char!(',') >>
+               zone_hours: opt!(complete!(map_res!(signed_number, parse_signed_num))) >>
+               char!(',') >>
+               zone_minutes: opt!(complete!(map_res!(signed_number, parse_num::<i32>))) >>
+               (time, day, month, year, zone_hours, zone_minutes)
+           ),
+           |data: ZdaFields| -> Result<ZdaData> {
+               let utc_date = match (data.1, data.2, data.3) {
+                   (Some(day), Some(month), Some(year)) => {
+                       if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+                           Err(ParseError::InvalidDate)?
+                       }
+                       Some(NaiveDate { year, month, day })
+                   }
+                   _ => None,
+               };
+               Ok(ZdaData {
+                   utc_time: data.0,
+                   utc_date,
+                   local_zone_hours: data.4.map(|(_, hours)| hours),
+                   local_zone_minutes: match (data.4, data.5) {
+                       (Some((true, _)), Some(minutes)) => Some(-minutes.abs()),
+                       (_, minutes) => minutes,
+                   },
+               })
+           }
+       )
+);
+
+/// Parse ZDA
+/// from gpsd:
+/// ZDA,160012.71,11,03,2004,-1,00*7D
+/// 1) UTC time (hours, minutes, seconds, may have fractional subsecond)
+/// 2) Day, 01 to 31
+/// 3) Month, 01 to 12
+/// 4) Year (4 digits)
+/// 5) Local zone description, 00 to +- 13 hours
+/// 6) Local zone minutes description, apply same sign as local hours
+/// 7) Checksum
+///
+/// Unlike RMC the year has four digits, so ZDA gives unambiguous date.
+/// Most receivers send zeros or empty fields for the local zone.
+pub fn parse_zda(sentence: &NmeaSentence) -> Result<ZdaData> {
+    if sentence.message_id != b"ZDA" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_zda(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GstData {
+    pub fix_time: Option<NaiveTime>,
+    /// RMS value of the standard deviation of the range inputs
+    pub rms: Option<f32>,
+    /// Standard deviation of semi-major axis of error ellipse, meters
+    pub semi_major: Option<f32>,
+    /// Standard deviation of semi-minor axis of error ellipse, meters
+    pub semi_minor: Option<f32>,
+    /// Orientation of semi-major axis of error ellipse, degrees from true north
+    pub orientation: Option<f32>,
+    /// Standard deviation of latitude error, meters
+    pub lat_error: Option<f32>,
+    /// Standard deviation of longitude error, meters
+    pub lon_error: Option<f32>,
+    /// Standard deviation of altitude error, meters
+    pub alt_error: Option<f32>,
+}
+
+type GstFields = (Option<NaiveTime>, Option<f32>, Option<f32>, Option<f32>, Option<f32>,
+                  Option<f32>, Option<f32>, Option<f32>);
+
+named!(do_parse_gst<GstData>,
+       map_res!(
+           do_parse!(
+               time: opt!(complete!(parse_hms)) >>
+               char!(',') >>
+               rms: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               semi_major: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               semi_minor: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               orientation: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               lat_error: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               lon_error: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               alt_error: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+               (time, rms, semi_major, semi_minor, orientation, lat_error, lon_error, alt_error)
+           ),
+           |data: GstFields| -> Result<GstData> {
+               Ok(GstData {
+                   fix_time: data.0,
+                   rms: data.1,
+                   semi_major: data.2,
+                   semi_minor: data.3,
+                   orientation: data.4,
+                   lat_error: data.5,
+                   lon_error: data.6,
+                   alt_error: data.7,
+               })
+           }
+       )
+);
+
+/// Parse GST
+/// from gpsd:
+/// GST,133006.00,3.6,4.1,2.7,127.4,3.6,4.0,6.2*50
+/// 1 UTC time of associated GGA fix
+/// 2 Total RMS standard deviation of ranges inputs to the navigation solution
+/// 3 Standard deviation (meters) of semi-major axis of error ellipse
+/// 4 Standard deviation (meters) of semi-minor axis of error ellipse
+/// 5 Orientation of semi-major axis of error ellipse (true north degrees)
+/// 6 Standard deviation (meters) of latitude error
+/// 7 Standard deviation (meters) of longitude error
+/// 8 Standard deviation (meters) of altitude error
+/// 9 Checksum
+///
+/// See `accuracy` module for error radius and ellipse helpers.
+pub fn parse_gst(sentence: &NmeaSentence) -> Result<GstData> {
+    if sentence.message_id != b"GST" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_gst(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Navigational status, NMEA 4.10 and later
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NavigationalStatus {
+    Safe,
+    Caution,
+    Unsafe,
+    NotValid,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GnsData {
+    pub fix_time: Option<NaiveTime>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Mode of every constellation, in order GPS, GLONASS, Galileo,
+    /// BeiDou, QZSS, NavIC
+    pub modes: Vec<FaaMode>,
+    pub fix_satellites: Option<u32>,
+    pub hdop: Option<f32>,
+    /// Orthometric height, meters
+    pub altitude: Option<f32>,
+    pub geoid_height: Option<f32>,
+    /// Age of differential data, seconds
+    pub dgps_age: Option<f32>,
+    pub dgps_station_id: Option<u16>,
+    pub nav_status: Option<NavigationalStatus>,
+}
+
+/// Constellations of GNS mode string, in order of characters
+const GNS_MODE_ORDER: [GnssType; 6] = [GnssType::Gps, GnssType::Glonass, GnssType::Galileo,
+                                       GnssType::Beidou, GnssType::Qzss, GnssType::Navic];
+
+impl GnsData {
+    /// Mode of the constellation, `None` if the mode string is too short
+    pub fn mode(&self, gnss_type: &GnssType) -> Option<FaaMode> {
+        GNS_MODE_ORDER
+            .iter()
+            .position(|v| v == gnss_type)
+            .and_then(|idx| self.modes.get(idx).cloned())
+    }
+
+    /// Constellations which contributed to the fix, with their fix types
+    pub fn contributing(&self) -> Vec<(GnssType, FixType)> {
+        GNS_MODE_ORDER
+            .iter()
+            .zip(self.modes.iter())
+            .filter(|&(_, mode)| *mode != FaaMode::NotValid)
+            .map(|(gnss_type, mode)| (gnss_type.clone(), FixType::from(*mode)))
+            .collect()
+    }
+
+    /// The best fix type of all constellations
+    pub fn fix_type(&self) -> FixType {
+        const RANK: [FaaMode; 8] = [FaaMode::RtkFixed, FaaMode::RtkFloat, FaaMode::Precise,
+                                    FaaMode::Differential, FaaMode::Autonomous,
+                                    FaaMode::Estimated, FaaMode::Manual, FaaMode::Simulator];
+        RANK.iter()
+            .find(|v| self.modes.contains(v))
+            .map(|v| FixType::from(*v))
+            .unwrap_or(FixType::Invalid)
+    }
+}
+
+type GnsFields<'a> = (Option<NaiveTime>, Option<(f64, f64)>, &'a [u8], Option<u32>,
+                      Option<f32>, Option<f32>, Option<f32>, Option<f32>, Option<u16>,
+                      Option<char>);
+
+named!(do_parse_gns<GnsData>,
+       map_res!(
+           do_parse!(
+               time: opt!(complete!(parse_hms)) >>
+               char!(',') >>
+               lat_lon: parse_lat_lon >>
+               char!(',') >>
+               modes: take_until!(",") >>
+               char!(',') >>
+               sats: opt!(map_res!(digit, parse_num::<u32>)) >>
+               char!(',') >>
+               hdop: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               altitude: opt!(complete!(map_res!(take_until!(","), parse_float_num::<f32>))) >>
+               char!(',') >>
+               geoid_height: opt!(complete!(map_res!(take_until!(","), parse_float_num::<f32>))) >>
+               char!(',') >>
+               dgps_age: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+               opt!(complete!(char!(','))) >>
+               dgps_station_id: opt!(complete!(map_res!(float_number, parse_num::<u16>))) >>
+               nav_status: opt!(complete!(preceded!(char!(','), one_of!("SCUV")))) >>
+               (time, lat_lon, modes, sats, hdop, altitude, geoid_height,
+                dgps_age, dgps_station_id, nav_status)
+           ),
+           |data: GnsFields| -> Result<GnsData> {
+               Ok(GnsData {
+                   fix_time: data.0,
+                   latitude: data.1.map(|v| v.0),
+                   longitude: data.1.map(|v| v.1),
+                   modes: data.2
+                       .iter()
+                       .map(|v| parse_faa_mode(*v as char))
+                       .collect::<Result<Vec<FaaMode>>>()?,
+                   fix_satellites: data.3,
+                   hdop: data.4,
+                   altitude: data.5,
+                   geoid_height: data.6,
+                   dgps_age: data.7,
+                   dgps_station_id: data.8,
+                   nav_status: data.9.map(|v| match v {
+                       'S' => NavigationalStatus::Safe,
+                       'C' => NavigationalStatus::Caution,
+                       'U' => NavigationalStatus::Unsafe,
+                       _ => NavigationalStatus::NotValid,
+                   }),
+               })
+           }
+       )
+);
+
+/// Parse GNS
+/// from NMEA 4.10:
+/// $GNGNS,122310.2,3722.425671,N,12258.856215,W,AAN,14,0.9,1005.543,6.5,,,V*40
+/// 1     122310.2       UTC of position fix
+/// 2,3   3722.425671,N  Latitude
+/// 4,5   12258.856215,W Longitude
+/// 6     AAN            Mode indicator, one character per constellation:
+/// GPS, GLONASS, Galileo, BeiDou, QZSS, NavIC.
+/// N = No fix, A = Autonomous, D = Differential, P = Precise,
+/// R = Real Time Kinematic, F = Float RTK, E = Estimated (dead reckoning),
+/// M = Manual input, S = Simulator
+/// 7     14             Total number of satellites in use, 00-99
+/// 8     0.9            HDOP
+/// 9     1005.543       Orthometric height, meters
+/// 10    6.5            Geoidal separation, meters
+/// 11                   Age of differential data
+/// 12                   Differential reference station ID
+/// 13    V              Navigational status (NMEA 4.10 and later):
+/// S = Safe, C = Caution, U = Unsafe, V = Not valid for navigation
+pub fn parse_gns(sentence: &NmeaSentence) -> Result<GnsData> {
+    if sentence.message_id != b"GNS" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_gns(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GbsData {
+    pub fix_time: Option<NaiveTime>,
+    /// Expected error in latitude, meters
+    pub lat_error: Option<f32>,
+    /// Expected error in longitude, meters
+    pub lon_error: Option<f32>,
+    /// Expected error in altitude, meters
+    pub alt_error: Option<f32>,
+    /// PRN of most likely failed satellite
+    pub failed_prn: Option<u32>,
+    /// Probability of missed detection for most likely failed satellite
+    pub missed_detection_probability: Option<f32>,
+    /// Estimate of bias on most likely failed satellite, meters
+    pub bias: Option<f32>,
+    /// Standard deviation of bias estimate
+    pub bias_std_dev: Option<f32>,
+    /// Constellation of failed satellite, from system ID or talker id
+    pub gnss_type: Option<GnssType>,
+    /// GNSS system ID, NMEA 4.10 and later
+    pub system_id: Option<u8>,
+    /// GNSS signal ID, NMEA 4.10 and later
+    pub signal_id: Option<u8>,
+}
+
+impl GbsData {
+    /// Constellation and native satellite number of the most likely failed
+    /// satellite, see `prn::classify`
+    pub fn failed_satellite(&self) -> Option<(GnssType, u32)> {
+        self.failed_prn
+            .and_then(|prn| prn::classify(self.gnss_type.clone(), prn))
+    }
+
+    /// Whether satellite reported by GSV is the most likely failed one
+    pub fn is_failed(&self, sat: &Satellite) -> bool {
+        match self.failed_satellite() {
+            Some(failed) => prn::classify(Some(sat.gnss_type.clone()), sat.prn) == Some(failed),
+            None => false,
+        }
+    }
+}
+
+type GbsFields = (Option<NaiveTime>, Option<f32>, Option<f32>, Option<f32>, Option<u32>,
+                  Option<f32>, Option<f32>, Option<f32>, Option<u8>, Option<u8>);
+
+named!(do_parse_gbs<GbsData>,
+       map_res!(
+           do_parse!(
+               time: opt!(complete!(parse_hms)) >>
+               char!(',') >>
+               lat_error: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               lon_error: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               alt_error: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               failed_prn: opt!(map_res!(digit, parse_num::<u32>)) >>
+               char!(',') >>
+               probability: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               bias: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               bias_std_dev: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+               opt!(complete!(char!(','))) >>
+               system_id: opt!(complete!(map_res!(take!(1), parse_hex))) >>
+               opt!(complete!(char!(','))) >>
+               signal_id: opt!(complete!(map_res!(take!(1), parse_hex))) >>
+               (time, lat_error, lon_error, alt_error, failed_prn, probability, bias,
+                bias_std_dev, system_id, signal_id)
+           ),
+           |data: GbsFields| -> Result<GbsData> {
+               Ok(GbsData {
+                   fix_time: data.0,
+                   lat_error: data.1,
+                   lon_error: data.2,
+                   alt_error: data.3,
+                   failed_prn: data.4,
+                   missed_detection_probability: data.5,
+                   bias: data.6,
+                   bias_std_dev: data.7,
+                   gnss_type: None,
+                   system_id: data.8,
+                   signal_id: data.9,
+               })
+           }
+       )
+);
+
+/// Parse GBS
+/// from NMEA 4.10:
+/// $GPGBS,015509.00,-0.031,-0.186,0.219,19,0.000,-0.354,6.972*4D
+/// 1 UTC time of the GGA or GNS fix associated with this sentence
+/// 2 Expected error in latitude, meters
+/// 3 Expected error in longitude, meters
+/// 4 Expected error in altitude, meters
+/// 5 ID number of most likely failed satellite
+/// 6 Probability of missed detection for most likely failed satellite
+/// 7 Estimate of bias in meters on most likely failed satellite
+/// 8 Standard deviation of bias estimate
+/// 9 GNSS system ID (NMEA 4.10 and later)
+/// 10 GNSS signal ID (NMEA 4.10 and later)
+pub fn parse_gbs(sentence: &NmeaSentence) -> Result<GbsData> {
+    if sentence.message_id != b"GBS" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    let mut ret = do_parse_gbs(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })?;
+    ret.gnss_type = match ret.system_id {
+        Some(id) => GnssType::from_system_id(id),
+        None => GnssType::from_talker_id(sentence.talker_id),
+    };
+    Ok(ret)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HdtData {
+    /// True heading, degrees
+    pub heading: Option<f32>,
+}
+
+named!(do_parse_hdt<HdtData>,
+       do_parse!(
+           heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(complete!(char!('T'))) >>
+           (HdtData { heading })
+       )
+);
+
+/// Parse HDT
+/// from gpsd:
+/// $HEHDT,4.0,T*2B
+/// 1 Heading, degrees true
+/// 2 T = True
+pub fn parse_hdt(sentence: &NmeaSentence) -> Result<HdtData> {
+    if sentence.message_id != b"HDT" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_hdt(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HdmData {
+    /// Magnetic heading, degrees
+    pub heading: Option<f32>,
+}
+
+named!(do_parse_hdm<HdmData>,
+       do_parse!(
+           heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(complete!(char!('M'))) >>
+           (HdmData { heading })
+       )
+);
+
+/// Parse HDM
+/// from gpsd:
+/// $HCHDM,238.5,M*25
+/// 1 Heading, degrees magnetic
+/// 2 M = Magnetic
+pub fn parse_hdm(sentence: &NmeaSentence) -> Result<HdmData> {
+    if sentence.message_id != b"HDM" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_hdm(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HdgData {
+    /// Magnetic sensor heading, degrees
+    pub heading: Option<f32>,
+    /// Magnetic deviation, degrees, positive to the east
+    pub deviation: Option<f32>,
+    /// Magnetic variation, degrees, positive to the east
+    pub variation: Option<f32>,
+}
+
+impl HdgData {
+    /// Magnetic heading: sensor heading corrected for deviation
+    ///
+    /// Missing deviation is taken as zero, since many compasses
+    /// are compensated and leave the field empty.
+    pub fn magnetic_heading(&self) -> Option<f32> {
+        self.heading
+            .map(|heading| normalize_degrees(heading + self.deviation.unwrap_or(0.)))
+    }
+
+    /// True heading: sensor heading corrected for deviation and variation
+    pub fn true_heading(&self) -> Option<f32> {
+        match (self.magnetic_heading(), self.variation) {
+            (Some(heading), Some(variation)) => Some(normalize_degrees(heading + variation)),
+            _ => None,
+        }
+    }
+}
+
+fn normalize_degrees(x: f32) -> f32 {
+    let x = x % 360.;
+    if x < 0. { x + 360. } else { x }
+}
+
+// Angle and E/W direction, east is positive
+named!(magnetic_correction<Option<f32>>,
+       do_parse!(
+           value: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           dir: opt!(complete!(one_of!("EW"))) >>
+           (value.map(|v| if dir == Some('W') { -v } else { v }))
+       )
+);
+
+named!(do_parse_hdg<HdgData>,
+       do_parse!(
+           heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           deviation: magnetic_correction >>
+           char!(',') >>
+           variation: magnetic_correction >>
+           (HdgData { heading, deviation, variation })
+       )
+);
+
+/// Parse HDG
+/// from gpsd:
+/// $HCHDG,101.1,,,7.1,W*3C
+/// 1 Magnetic sensor heading, degrees
+/// 2 Magnetic deviation, degrees
+/// 3 Magnetic deviation direction, E = Easterly, W = Westerly
+/// 4 Magnetic variation, degrees
+/// 5 Magnetic variation direction, E = Easterly, W = Westerly
+///
+/// Easterly deviation or variation is added to the heading, westerly
+/// is subtracted, see `HdgData::true_heading`.
+pub fn parse_hdg(sentence: &NmeaSentence) -> Result<HdgData> {
+    if sentence.message_id != b"HDG" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_hdg(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ThsData {
+    /// True heading, degrees
+    pub heading: Option<f32>,
+    pub mode: Option<FaaMode>,
+}
+
+named!(do_parse_ths<ThsData>,
+       map_res!(
+           do_parse!(
+               heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               mode: opt!(complete!(one_of!("AEMSV"))) >>
+               (heading, mode)
+           ),
+           |data: (Option<f32>, Option<char>)| -> Result<ThsData> {
+               Ok(ThsData {
+                   heading: data.0,
+                   mode: match data.1 {
+                       Some('V') => Some(FaaMode::NotValid),
+                       Some(mode) => Some(parse_faa_mode(mode)?),
+                       None => None,
+                   },
+               })
+           }
+       )
+);
+
+/// Parse THS
+/// from NMEA 3.0:
+/// $INTHS,77.52,E*24
+/// 1 Heading, degrees true
+/// 2 Mode indicator: A = Autonomous, E = Estimated (dead reckoning),
+/// M = Manual input, S = Simulator, V = Data not valid
+pub fn parse_ths(sentence: &NmeaSentence) -> Result<ThsData> {
+    if sentence.message_id != b"THS" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_ths(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Convert speed to knots, `unit` is NMEA unit letter:
+/// N = knots, K = km/h, M = m/s, S = statute miles per hour
+fn speed_to_knots(speed: f32, unit: char) -> Option<f32> {
+    match unit {
+        'N' => Some(speed),
+        'K' => Some(speed / 1.852),
+        'M' => Some(speed * 3600. / 1852.),
+        'S' => Some(speed * 1609.344 / 1852.),
+        _ => None,
+    }
+}
+
+/// Reference of wind angle
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WindReference {
+    /// Relative to the bow, apparent wind
+    Relative,
+    /// Relative to the bow, theoretical (true) wind computed from
+    /// the apparent wind and the speed of the vessel
+    Theoretical,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MwvData {
+    /// Wind angle, degrees clockwise from the bow, 0 to 359
+    pub wind_angle: Option<f32>,
+    pub reference: Option<WindReference>,
+    /// Wind speed, knots
+    pub wind_speed: Option<f32>,
+    pub valid: bool,
+}
+
+type MwvFields = (Option<f32>, Option<char>, Option<f32>, Option<char>, Option<char>);
+
+named!(do_parse_mwv<MwvData>,
+       map_res!(
+           do_parse!(
+               angle: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               reference: opt!(one_of!("RT")) >>
+               char!(',') >>
+               speed: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               unit: opt!(one_of!("KMNS")) >>
+               char!(',') >>
+               status: opt!(complete!(one_of!("AV"))) >>
+               (angle, reference, speed, unit, status)
+           ),
+           |data: MwvFields| -> Result<MwvData> {
+               Ok(MwvData {
+                   wind_angle: data.0,
+                   reference: data.1.map(|v| match v {
+                       'R' => WindReference::Relative,
+                       _ => WindReference::Theoretical,
+                   }),
+                   wind_speed: match (data.2, data.3) {
+                       (Some(speed), Some(unit)) => speed_to_knots(speed, unit),
+                       _ => None,
+                   },
+                   valid: data.4 == Some('A'),
+               })
+           }
+       )
+);
+
+/// Parse MWV
+/// from NMEA 3.0:
+/// $WIMWV,214.8,R,0.1,K,A*28
+/// 1 Wind angle, 0 to 359 degrees
+/// 2 Reference, R = Relative, T = Theoretical
+/// 3 Wind speed
+/// 4 Wind speed units, K = km/h, M = m/s, N = knots, S = statute mph
+/// 5 Status, A = Data valid, V = Invalid
+///
+/// Wind speed is converted to knots.
+pub fn parse_mwv(sentence: &NmeaSentence) -> Result<MwvData> {
+    if sentence.message_id != b"MWV" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_mwv(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MwdData {
+    /// Direction the wind is blowing from, degrees true
+    pub true_direction: Option<f32>,
+    /// Direction the wind is blowing from, degrees magnetic
+    pub magnetic_direction: Option<f32>,
+    /// Wind speed, knots
+    pub wind_speed: Option<f32>,
+}
+
+named!(do_parse_mwd<MwdData>,
+       do_parse!(
+           true_direction: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('T')) >>
+           char!(',') >>
+           magnetic_direction: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           knots: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           mps: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('M'))) >>
+           (MwdData {
+               true_direction,
+               magnetic_direction,
+               wind_speed: knots.or_else(|| mps.and_then(|v| speed_to_knots(v, 'M'))),
+           })
+       )
+);
+
+/// Parse MWD
+/// from NMEA 3.0:
+/// $WIMWD,10.1,T,10.1,M,12,N,40,M*5D
+/// 1,2 Wind direction, degrees true
+/// 3,4 Wind direction, degrees magnetic
+/// 5,6 Wind speed, knots
+/// 7,8 Wind speed, meters/second
+///
+/// Speed in knots is preferred, speed in m/s is converted if it is missing.
+pub fn parse_mwd(sentence: &NmeaSentence) -> Result<MwdData> {
+    if sentence.message_id != b"MWD" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_mwd(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VwrData {
+    /// Relative wind angle, degrees from the bow, 0 to 180,
+    /// positive to starboard, negative to port
+    pub wind_angle: Option<f32>,
+    /// Relative wind speed, knots
+    pub wind_speed: Option<f32>,
+}
+
+impl VwrData {
+    /// Wind angle as in MWV with relative reference:
+    /// clockwise from the bow, 0 to 359 degrees
+    pub fn wind_angle_clockwise(&self) -> Option<f32> {
+        self.wind_angle
+            .map(|v| if v < 0. { v + 360. } else { v })
+    }
+}
+
+named!(do_parse_vwr<VwrData>,
+       do_parse!(
+           angle: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           side: opt!(one_of!("LR")) >>
+           char!(',') >>
+           knots: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           mps: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           kph: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('K'))) >>
+           (VwrData {
+               wind_angle: angle.map(|v| if side == Some('L') { -v } else { v }),
+               wind_speed: knots
+                   .or_else(|| mps.and_then(|v| speed_to_knots(v, 'M')))
+                   .or_else(|| kph.and_then(|v| speed_to_knots(v, 'K'))),
+           })
+       )
+);
+
+/// Parse VWR
+/// from NMEA 2.3 (deprecated, replaced by MWV):
+/// $IIVWR,148.0,L,02.4,N,01.2,M,04.4,K*6F
+/// 1,2 Wind direction relative to the bow, 0 to 180 degrees,
+/// L = port, R = starboard
+/// 3,4 Wind speed, knots
+/// 5,6 Wind speed, meters/second
+/// 7,8 Wind speed, km/h
+pub fn parse_vwr(sentence: &NmeaSentence) -> Result<VwrData> {
+    if sentence.message_id != b"VWR" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_vwr(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+// Depth given in feet, meters and fathoms, as in DBT and DBS.
+// Meters are preferred, feet or fathoms are converted if they are missing.
+named!(depth_in_units<Option<f32>>,
+       do_parse!(
+           feet: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('f')) >>
+           char!(',') >>
+           meters: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           fathoms: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('F'))) >>
+           (meters
+                .or_else(|| feet.map(|v| v * 0.3048))
+                .or_else(|| fathoms.map(|v| v * 1.8288)))
+       )
+);
+
+#[derive(Debug, PartialEq)]
+pub struct DbtData {
+    /// Depth below transducer, meters
+    pub depth: Option<f32>,
+}
+
+/// Parse DBT
+/// from gpsd:
+/// $SDDBT,7.8,f,2.4,M,1.3,F*0D
+/// 1,2 Depth below transducer, feet
+/// 3,4 Depth below transducer, meters
+/// 5,6 Depth below transducer, fathoms
+pub fn parse_dbt(sentence: &NmeaSentence) -> Result<DbtData> {
+    if sentence.message_id != b"DBT" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    depth_in_units(sentence.data)
+        .map(|(_, depth)| DbtData { depth })
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DbsData {
+    /// Depth below surface, meters
+    pub depth: Option<f32>,
+}
+
+/// Parse DBS
+/// from NMEA 3.0:
+/// $SDDBS,25.4,f,7.7,M,4.2,F*34
+/// 1,2 Depth below surface, feet
+/// 3,4 Depth below surface, meters
+/// 5,6 Depth below surface, fathoms
+pub fn parse_dbs(sentence: &NmeaSentence) -> Result<DbsData> {
+    if sentence.message_id != b"DBS" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    depth_in_units(sentence.data)
+        .map(|(_, depth)| DbsData { depth })
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DptData {
+    /// Depth below transducer, meters
+    pub depth: Option<f32>,
+    /// Transducer offset, meters: positive is distance from transducer
+    /// to water line, negative is distance from transducer to keel
+    pub offset: Option<f32>,
+    /// Maximum range scale in use, meters, NMEA 3.0 and later
+    pub max_range: Option<f32>,
+}
+
+impl DptData {
+    /// Depth below surface, if the offset is given relative to water line
+    pub fn depth_below_surface(&self) -> Option<f32> {
+        match (self.depth, self.offset) {
+            (Some(depth), Some(offset)) if offset >= 0. => Some(depth + offset),
+            _ => None,
+        }
+    }
+
+    /// Depth below keel, if the offset is given relative to keel
+    pub fn depth_below_keel(&self) -> Option<f32> {
+        match (self.depth, self.offset) {
+            (Some(depth), Some(offset)) if offset <= 0. => Some(depth + offset),
+            _ => None,
+        }
+    }
+}
+
+named!(do_parse_dpt<DptData>,
+       do_parse!(
+           depth: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           offset: opt!(complete!(map_res!(signed_number, parse_float_num::<f32>))) >>
+           max_range: opt!(complete!(preceded!(char!(','),
+                                               map_res!(float_number, parse_float_num::<f32>)))) >>
+           (DptData { depth, offset, max_range })
+       )
+);
+
+/// Parse DPT
+/// from NMEA 3.0:
+/// $INDPT,2.3,0.0*46
+/// $SDDPT,15.2,-1.5,100*55
+/// 1 Water depth relative to transducer, meters
+/// 2 Offset from transducer, meters: positive means distance from
+/// transducer to water line, negative means distance from transducer to keel
+/// 3 Maximum range scale in use, meters (NMEA 3.0 and later)
+pub fn parse_dpt(sentence: &NmeaSentence) -> Result<DptData> {
+    if sentence.message_id != b"DPT" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_dpt(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VhwData {
+    /// Heading, degrees true
+    pub true_heading: Option<f32>,
+    /// Heading, degrees magnetic
+    pub magnetic_heading: Option<f32>,
+    /// Speed through water, knots
+    pub speed_through_water: Option<f32>,
+}
+
+named!(do_parse_vhw<VhwData>,
+       do_parse!(
+           true_heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('T')) >>
+           char!(',') >>
+           magnetic_heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           knots: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           kph: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('K'))) >>
+           (VhwData {
+               true_heading,
+               magnetic_heading,
+               speed_through_water: knots.or_else(|| kph.map(|v| v / 1.852)),
+           })
+       )
+);
+
+/// Parse VHW
+/// from gpsd:
+/// $VWVHW,45.0,T,43.0,M,3.5,N,6.4,K*56
+/// 1,2 Heading, degrees true
+/// 3,4 Heading, degrees magnetic
+/// 5,6 Speed through water, knots
+/// 7,8 Speed through water, km/h
+///
+/// Speed in km/h is converted to knots if speed in knots is missing.
+pub fn parse_vhw(sentence: &NmeaSentence) -> Result<VhwData> {
+    if sentence.message_id != b"VHW" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_vhw(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Speeds are in knots, longitudinal speed is positive ahead,
+/// transverse speed is positive to starboard
+#[derive(Debug, PartialEq)]
+pub struct VbwData {
+    pub water_speed_longitudinal: Option<f32>,
+    pub water_speed_transverse: Option<f32>,
+    pub water_speed_valid: bool,
+    pub ground_speed_longitudinal: Option<f32>,
+    pub ground_speed_transverse: Option<f32>,
+    pub ground_speed_valid: bool,
+    /// NMEA 3.0 and later
+    pub stern_water_speed_transverse: Option<f32>,
+    pub stern_water_speed_valid: bool,
+    /// NMEA 3.0 and later
+    pub stern_ground_speed_transverse: Option<f32>,
+    pub stern_ground_speed_valid: bool,
+}
+
+type VbwStern = (Option<f32>, Option<char>, Option<f32>, Option<char>);
+
+named!(do_parse_vbw_stern<VbwStern>,
+       do_parse!(
+           char!(',') >>
+           water: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           water_status: opt!(one_of!("AV")) >>
+           char!(',') >>
+           ground: opt!(complete!(map_res!(signed_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           ground_status: opt!(complete!(one_of!("AV"))) >>
+           (water, water_status, ground, ground_status)
+       )
+);
+
+type VbwFields = (Option<f32>, Option<f32>, Option<char>, Option<f32>, Option<f32>, Option<char>,
+                  VbwStern);
+
+named!(do_parse_vbw<VbwData>,
+       map_res!(
+           do_parse!(
+               water_long: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               water_trans: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               water_status: opt!(one_of!("AV")) >>
+               char!(',') >>
+               ground_long: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               ground_trans: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               ground_status: opt!(complete!(one_of!("AV"))) >>
+               stern: opt!(complete!(do_parse_vbw_stern)) >>
+               (water_long, water_trans, water_status, ground_long, ground_trans,
+                ground_status, stern.unwrap_or((None, None, None, None)))
+           ),
+           |data: VbwFields| -> Result<VbwData> {
+               Ok(VbwData {
+                   water_speed_longitudinal: data.0,
+                   water_speed_transverse: data.1,
+                   water_speed_valid: data.2 == Some('A'),
+                   ground_speed_longitudinal: data.3,
+                   ground_speed_transverse: data.4,
+                   ground_speed_valid: data.5 == Some('A'),
+                   stern_water_speed_transverse: (data.6).0,
+                   stern_water_speed_valid: (data.6).1 == Some('A'),
+                   stern_ground_speed_transverse: (data.6).2,
+                   stern_ground_speed_valid: (data.6).3 == Some('A'),
+               })
+           }
+       )
+);
+
+/// Parse VBW
+/// from NMEA 3.0:
+/// $VMVBW,12.3,0.07,A,11.78,0.12,A,0.02,A,0.1,V*47
+/// 1 Longitudinal water speed, knots, "-" means astern
+/// 2 Transverse water speed, knots, "-" means port
+/// 3 Status: water speed, A = Data valid, V = Data invalid
+/// 4 Longitudinal ground speed, knots, "-" means astern
+/// 5 Transverse ground speed, knots, "-" means port
+/// 6 Status: ground speed, A = Data valid, V = Data invalid
+/// 7 Stern transverse water speed, knots (NMEA 3.0 and later)
+/// 8 Status: stern water speed (NMEA 3.0 and later)
+/// 9 Stern transverse ground speed, knots (NMEA 3.0 and later)
+/// 10 Status: stern ground speed (NMEA 3.0 and later)
+pub fn parse_vbw(sentence: &NmeaSentence) -> Result<VbwData> {
+    if sentence.message_id != b"VBW" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_vbw(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Distances are in nautical miles
+#[derive(Debug, PartialEq)]
+pub struct VlwData {
+    pub total_water_distance: Option<f32>,
+    /// Water distance since reset
+    pub trip_water_distance: Option<f32>,
+    /// NMEA 3.0 and later
+    pub total_ground_distance: Option<f32>,
+    /// Ground distance since reset, NMEA 3.0 and later
+    pub trip_ground_distance: Option<f32>,
+}
+
+named!(do_parse_vlw_ground<(Option<f32>, Option<f32>)>,
+       do_parse!(
+           char!(',') >>
+           total: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           trip: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('N'))) >>
+           (total, trip)
+       )
+);
+
+named!(do_parse_vlw<VlwData>,
+       do_parse!(
+           total: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           trip: opt!(complete!(map_res!(float_number, parse_float_num::<f32>))) >>
+           char!(',') >>
+           opt!(complete!(char!('N'))) >>
+           ground: opt!(complete!(do_parse_vlw_ground)) >>
+           (VlwData {
+               total_water_distance: total,
+               trip_water_distance: trip,
+               total_ground_distance: ground.and_then(|v| v.0),
+               trip_ground_distance: ground.and_then(|v| v.1),
+           })
+       )
+);
+
+/// Parse VLW
+/// from NMEA 3.0:
+/// $IIVLW,7803.2,N,0.00,N*43
+/// $IIVLW,115.2,N,1.2,N,122.5,N,1.3,N*4F
+/// 1,2 Total cumulative water distance, nautical miles
+/// 3,4 Water distance since reset, nautical miles
+/// 5,6 Total cumulative ground distance, nautical miles (NMEA 3.0 and later)
+/// 7,8 Ground distance since reset, nautical miles (NMEA 3.0 and later)
+pub fn parse_vlw(sentence: &NmeaSentence) -> Result<VlwData> {
+    if sentence.message_id != b"VLW" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_vlw(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Direction to steer to get back on track
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SteerDirection {
+    Left,
+    Right,
+}
+
+/// Bearing, degrees, with its reference
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Bearing {
+    True(f32),
+    Magnetic(f32),
+    /// Relative to own ship's heading, as reported by radar
+    Relative(f32),
+}
+
+impl Bearing {
+    pub fn degrees(&self) -> f32 {
+        match *self {
+            Bearing::True(x) | Bearing::Magnetic(x) | Bearing::Relative(x) => x,
+        }
+    }
+}
+
+fn parse_text_field(data: &[u8]) -> Result<Option<String>> {
+    if data.is_empty() {
+        return Ok(None);
+    }
+    str::from_utf8(data)
+        .map(|v| Some(String::from(v)))
+        .map_err(|_| ParseError::Nom)
+}
+
+named!(waypoint_id<Option<String>>,
+       map_res!(alt_complete!(take_until!(",") | nom::rest), parse_text_field));
+
+named!(steer_direction<SteerDirection>,
+       map!(one_of!("LR"), |v| if v == 'L' { SteerDirection::Left } else { SteerDirection::Right }));
+
+named!(faa_mode_field<FaaMode>, map_res!(one_of!("ADEMSN"), parse_faa_mode));
+
+// Angle followed by M or T reference, bearing without reference is taken as true
+named!(bearing<Option<Bearing>>,
+       do_parse!(
+           angle: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           reference: opt!(complete!(one_of!("MT"))) >>
+           (angle.map(|v| if reference == Some('M') { Bearing::Magnetic(v) } else { Bearing::True(v) }))
+       )
+);
+
+#[derive(Debug, PartialEq)]
+pub struct XteData {
+    /// Both status fields are valid
+    pub valid: bool,
+    /// Magnitude of cross track error, nautical miles
+    pub cross_track_error: Option<f32>,
+    pub steer: Option<SteerDirection>,
+    pub faa_mode: Option<FaaMode>,
+}
+
+named!(do_parse_xte<XteData>,
+       do_parse!(
+           status1: one_of!("AV") >>
+           char!(',') >>
+           status2: one_of!("AV") >>
+           char!(',') >>
+           cross_track_error: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           steer: opt!(steer_direction) >>
+           char!(',') >>
+           opt!(complete!(char!('N'))) >>
+           faa_mode: opt!(complete!(preceded!(char!(','), faa_mode_field))) >>
+           (XteData {
+               valid: status1 == 'A' && status2 == 'A',
+               cross_track_error,
+               steer,
+               faa_mode,
+           })
+       )
+);
+
+/// Parse XTE
+/// from gpsd:
+/// $GPXTE,A,A,0.67,L,N*6F
+/// 1 Status: A = Valid, V = Loran-C blink or SNR warning, or general warning
+/// 2 Status: A = Valid, V = Loran-C cycle lock warning
+/// 3 Cross track error magnitude
+/// 4 Direction to steer, L or R
+/// 5 Cross track units, N = nautical miles
+/// 6 FAA mode indicator (NMEA 2.3 and later)
+pub fn parse_xte(sentence: &NmeaSentence) -> Result<XteData> {
+    if sentence.message_id != b"XTE" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_xte(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ApbData {
+    /// Both status fields are valid
+    pub valid: bool,
+    /// Magnitude of cross track error, nautical miles
+    pub cross_track_error: Option<f32>,
+    pub steer: Option<SteerDirection>,
+    pub arrival_circle_entered: bool,
+    pub perpendicular_passed: bool,
+    pub bearing_origin_to_destination: Option<Bearing>,
+    pub destination_id: Option<String>,
+    pub bearing_to_destination: Option<Bearing>,
+    pub heading_to_steer: Option<Bearing>,
+    pub faa_mode: Option<FaaMode>,
+}
+
+named!(do_parse_apb<ApbData>,
+       do_parse!(
+           status1: one_of!("AV") >>
+           char!(',') >>
+           status2: one_of!("AV") >>
+           char!(',') >>
+           cross_track_error: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           steer: opt!(steer_direction) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           arrival: opt!(one_of!("AV")) >>
+           char!(',') >>
+           perpendicular: opt!(one_of!("AV")) >>
+           char!(',') >>
+           bearing_origin_to_destination: bearing >>
+           char!(',') >>
+           destination_id: waypoint_id >>
+           char!(',') >>
+           bearing_to_destination: bearing >>
+           char!(',') >>
+           heading_to_steer: bearing >>
+           faa_mode: opt!(complete!(preceded!(char!(','), faa_mode_field))) >>
+           (ApbData {
+               valid: status1 == 'A' && status2 == 'A',
+               cross_track_error,
+               steer,
+               arrival_circle_entered: arrival == Some('A'),
+               perpendicular_passed: perpendicular == Some('A'),
+               bearing_origin_to_destination,
+               destination_id,
+               bearing_to_destination,
+               heading_to_steer,
+               faa_mode,
+           })
+       )
+);
+
+/// Parse APB
+/// from gpsd:
+/// $GPAPB,A,A,0.10,R,N,V,V,011,M,DEST,011,M,011,M*3C
+/// 1 Status: A = Valid, V = Loran-C blink or SNR warning, or general warning
+/// 2 Status: A = Valid, V = Loran-C cycle lock warning
+/// 3 Cross track error magnitude
+/// 4 Direction to steer, L or R
+/// 5 Cross track units, N = nautical miles
+/// 6 Status: A = Arrival circle entered
+/// 7 Status: A = Perpendicular passed at waypoint
+/// 8,9 Bearing origin to destination, M = magnetic, T = true
+/// 10 Destination waypoint ID
+/// 11,12 Bearing, present position to destination, M = magnetic, T = true
+/// 13,14 Heading to steer to destination waypoint, M = magnetic, T = true
+/// 15 FAA mode indicator (NMEA 2.3 and later)
+pub fn parse_apb(sentence: &NmeaSentence) -> Result<ApbData> {
+    if sentence.message_id != b"APB" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_apb(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RmbData {
+    pub valid: bool,
+    /// Magnitude of cross track error, nautical miles
+    pub cross_track_error: Option<f32>,
+    pub steer: Option<SteerDirection>,
+    pub origin_id: Option<String>,
+    pub destination_id: Option<String>,
+    pub destination_latitude: Option<f64>,
+    pub destination_longitude: Option<f64>,
+    /// Range to destination, nautical miles
+    pub range_to_destination: Option<f32>,
+    /// Bearing to destination, degrees true
+    pub bearing_to_destination: Option<f32>,
+    /// Destination closing velocity, knots
+    pub closing_velocity: Option<f32>,
+    pub arrived: bool,
+    pub faa_mode: Option<FaaMode>,
+}
+
+named!(do_parse_rmb<RmbData>,
+       do_parse!(
+           status: one_of!("AV") >>
+           char!(',') >>
+           cross_track_error: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           steer: opt!(steer_direction) >>
+           char!(',') >>
+           origin_id: waypoint_id >>
+           char!(',') >>
+           destination_id: waypoint_id >>
+           char!(',') >>
+           lat_lon: parse_lat_lon >>
+           char!(',') >>
+           range: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           bearing: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           closing_velocity: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           arrival: opt!(complete!(one_of!("AV"))) >>
+           faa_mode: opt!(complete!(preceded!(char!(','), faa_mode_field))) >>
+           (RmbData {
+               valid: status == 'A',
+               cross_track_error,
+               steer,
+               origin_id,
+               destination_id,
+               destination_latitude: lat_lon.map(|v| v.0),
+               destination_longitude: lat_lon.map(|v| v.1),
+               range_to_destination: range,
+               bearing_to_destination: bearing,
+               closing_velocity,
+               arrived: arrival == Some('A'),
+               faa_mode,
+           })
+       )
+);
+
+/// Parse RMB
+/// from gpsd:
+/// $GPRMB,A,0.66,L,003,004,4917.24,N,12309.57,W,001.3,052.5,000.5,V*20
+/// 1 Status: A = Valid, V = Navigation receiver warning
+/// 2 Cross track error, nautical miles, 9.99 max
+/// 3 Direction to steer, L or R
+/// 4 Origin waypoint ID
+/// 5 Destination waypoint ID
+/// 6,7 Destination waypoint latitude
+/// 8,9 Destination waypoint longitude
+/// 10 Range to destination, nautical miles, 999.9 max
+/// 11 True bearing to destination
+/// 12 Velocity towards destination, knots
+/// 13 Arrival status: A = Arrival circle entered, V = Not entered
+/// 14 FAA mode indicator (NMEA 2.3 and later)
+pub fn parse_rmb(sentence: &NmeaSentence) -> Result<RmbData> {
+    if sentence.message_id != b"RMB" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_rmb(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BwcData {
+    pub fix_time: Option<NaiveTime>,
+    pub waypoint_latitude: Option<f64>,
+    pub waypoint_longitude: Option<f64>,
+    /// Bearing to waypoint, degrees true
+    pub true_bearing: Option<f32>,
+    /// Bearing to waypoint, degrees magnetic
+    pub magnetic_bearing: Option<f32>,
+    /// Distance to waypoint, nautical miles
+    pub distance: Option<f32>,
+    pub waypoint_id: Option<String>,
+    pub faa_mode: Option<FaaMode>,
+}
+
+named!(do_parse_bwc<BwcData>,
+       do_parse!(
+           time: opt!(complete!(parse_hms)) >>
+           char!(',') >>
+           lat_lon: parse_lat_lon >>
+           char!(',') >>
+           true_bearing: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('T')) >>
+           char!(',') >>
+           magnetic_bearing: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           distance: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           waypoint_id: waypoint_id >>
+           faa_mode: opt!(complete!(preceded!(char!(','), faa_mode_field))) >>
+           (BwcData {
+               fix_time: time,
+               waypoint_latitude: lat_lon.map(|v| v.0),
+               waypoint_longitude: lat_lon.map(|v| v.1),
+               true_bearing,
+               magnetic_bearing,
+               distance,
+               waypoint_id,
+               faa_mode,
+           })
+       )
+);
+
+/// Parse BWC
+/// from gpsd:
+/// $GPBWC,220516,5130.02,N,00046.34,W,213.8,T,218.0,M,0004.6,N,EGLM*21
+/// 1 UTC time of fix
+/// 2,3 Waypoint latitude
+/// 4,5 Waypoint longitude
+/// 6,7 Bearing to waypoint, degrees true
+/// 8,9 Bearing to waypoint, degrees magnetic
+/// 10,11 Distance to waypoint, nautical miles
+/// 12 Waypoint ID
+/// 13 FAA mode indicator (NMEA 2.3 and later)
+pub fn parse_bwc(sentence: &NmeaSentence) -> Result<BwcData> {
+    if sentence.message_id != b"BWC" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_bwc(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BodData {
+    /// Bearing from origin to destination, degrees true
+    pub true_bearing: Option<f32>,
+    /// Bearing from origin to destination, degrees magnetic
+    pub magnetic_bearing: Option<f32>,
+    pub destination_id: Option<String>,
+    pub origin_id: Option<String>,
+}
+
+named!(do_parse_bod<BodData>,
+       do_parse!(
+           true_bearing: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('T')) >>
+           char!(',') >>
+           magnetic_bearing: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           destination_id: waypoint_id >>
+           origin_id: opt!(complete!(preceded!(char!(','), waypoint_id))) >>
+           (BodData {
+               true_bearing,
+               magnetic_bearing,
+               destination_id,
+               origin_id: origin_id.and_then(|v| v),
+           })
+       )
+);
+
+/// Parse BOD
+/// from gpsd:
+/// $GPBOD,099.3,T,105.6,M,POINTB,*48
+/// 1,2 Bearing from origin to destination, degrees true
+/// 3,4 Bearing from origin to destination, degrees magnetic
+/// 5 Destination waypoint ID
+/// 6 Origin waypoint ID
+pub fn parse_bod(sentence: &NmeaSentence) -> Result<BodData> {
+    if sentence.message_id != b"BOD" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_bod(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct WplData {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub waypoint_id: Option<String>,
+}
+
+named!(do_parse_wpl<WplData>,
+       do_parse!(
+           lat_lon: parse_lat_lon >>
+           char!(',') >>
+           waypoint_id: waypoint_id >>
+           (WplData {
+               latitude: lat_lon.map(|v| v.0),
+               longitude: lat_lon.map(|v| v.1),
+               waypoint_id,
+           })
+       )
+);
+
+/// Parse WPL
+/// from gpsd:
+/// $GPWPL,4917.16,N,12310.64,W,003*65
+/// 1,2 Latitude
+/// 3,4 Longitude
+/// 5 Waypoint ID
+pub fn parse_wpl(sentence: &NmeaSentence) -> Result<WplData> {
+    if sentence.message_id != b"WPL" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_wpl(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RteMode {
+    /// Complete list of waypoints of the route
+    Complete,
+    /// Working route: the first waypoint is the one just left,
+    /// the second is the destination, the rest are the remaining route
+    Working,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RteData {
+    pub number_of_sentences: u16,
+    pub sentence_num: u16,
+    pub mode: RteMode,
+    pub route_id: Option<String>,
+    pub waypoint_ids: Vec<String>,
+}
+
+named!(do_parse_rte<RteData>,
+       do_parse!(
+           number_of_sentences: map_res!(digit, parse_num::<u16>) >>
+           char!(',') >>
+           sentence_num: map_res!(digit, parse_num::<u16>) >>
+           char!(',') >>
+           mode: one_of!("cwCW") >>
+           char!(',') >>
+           route_id: waypoint_id >>
+           waypoint_ids: many0!(complete!(preceded!(char!(','), waypoint_id))) >>
+           (RteData {
+               number_of_sentences,
+               sentence_num,
+               mode: if mode == 'c' || mode == 'C' { RteMode::Complete } else { RteMode::Working },
+               route_id,
+               waypoint_ids: waypoint_ids.into_iter().flatten().collect(),
+           })
+       )
+);
+
+/// Parse RTE
+/// from gpsd:
+/// $GPRTE,2,1,c,0,PBRCPK,PBRTO,PTELGR,PPLAND,PYAMBU,PPFAIR,PWARRN,PMORTL,PLISMR*73
+/// $GPRTE,2,2,c,0,PCRESY,GRYRIE,GCORIO,GWERR,GWESTG,7FED*34
+/// 1 Total number of sentences needed for full data
+/// 2 Sentence number
+/// 3 Mode: c = complete route, w = working route
+/// 4 Route ID
+/// 5- Waypoint IDs
+///
+/// See `route::RouteAssembler` to join the sentences of one route.
+pub fn parse_rte(sentence: &NmeaSentence) -> Result<RteData> {
+    if sentence.message_id != b"RTE" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_rte(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Encapsulated AIS sentence, one fragment of AIS message
+#[derive(Debug, PartialEq)]
+pub struct VdmData {
+    /// VDO, report of own vessel, instead of VDM
+    pub own_vessel: bool,
+    pub fragment_count: u8,
+    pub fragment_num: u8,
+    /// Sequential message ID, tells apart fragments of interleaved
+    /// multi-fragment messages
+    pub message_id: Option<u8>,
+    /// Radio channel, A or B, some receivers send 1 or 2
+    pub channel: Option<char>,
+    /// 6-bit armoured payload, see `ais::dearmor`
+    pub payload: Vec<u8>,
+    /// Number of fill bits added to the payload to make it a multiple
+    /// of 6 bits
+    pub fill_bits: u8,
+}
+
+named!(do_parse_vdm<VdmData>,
+       do_parse!(
+           fragment_count: map_res!(digit, parse_num::<u8>) >>
+           char!(',') >>
+           fragment_num: map_res!(digit, parse_num::<u8>) >>
+           char!(',') >>
+           message_id: opt!(map_res!(digit, parse_num::<u8>)) >>
+           char!(',') >>
+           channel: opt!(one_of!("AB12")) >>
+           char!(',') >>
+           payload: take_until!(",") >>
+           char!(',') >>
+           fill_bits: one_of!("012345") >>
+           (VdmData {
+               own_vessel: false,
+               fragment_count,
+               fragment_num,
+               message_id,
+               channel,
+               payload: payload.to_vec(),
+               fill_bits: fill_bits as u8 - b'0',
+           })
+       )
+);
+
+/// Parse VDM or VDO
+/// from gpsd:
+/// !AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C
+/// !AIVDM,2,1,3,B,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0*3E
+/// !AIVDM,2,2,3,B,1@0000000000000,2*55
+/// 1 Count of fragments in the currently accumulating message
+/// 2 Fragment number of this sentence, one-based
+/// 3 Sequential message ID for multi-sentence messages
+/// 4 Radio channel code, A or B
+/// 5 Data payload, 6-bit armoured
+/// 6 Number of fill bits
+///
+/// See `ais::AisAssembler` to join fragments and decode the payload.
+pub fn parse_vdm(sentence: &NmeaSentence) -> Result<VdmData> {
+    let own_vessel = match sentence.message_id {
+        b"VDM" => false,
+        b"VDO" => true,
+        _ => Err(ParseError::InvalidMessageId)?,
+    };
+    do_parse_vdm(sentence.data)
+        .map(|(_, o)| VdmData { own_vessel, ..o })
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Severity of TXT message, from the text identifier field as used
+/// by u-blox and MediaTek receivers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TxtSeverity {
+    Error,
+    Warning,
+    Notice,
+    User,
+    Other(u8),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TxtData {
+    pub number_of_sentences: u8,
+    pub sentence_num: u8,
+    /// Text identifier, the same for all sentences of one message
+    pub text_id: u8,
+    pub text: String,
+}
+
+impl TxtData {
+    pub fn severity(&self) -> TxtSeverity {
+        match self.text_id {
+            0 => TxtSeverity::Error,
+            1 => TxtSeverity::Warning,
+            2 => TxtSeverity::Notice,
+            7 => TxtSeverity::User,
+            x => TxtSeverity::Other(x),
+        }
+    }
+}
+
+named!(do_parse_txt<TxtData>,
+       map_res!(
+           do_parse!(
+               number_of_sentences: map_res!(digit, parse_num::<u8>) >>
+               char!(',') >>
+               sentence_num: map_res!(digit, parse_num::<u8>) >>
+               char!(',') >>
+               text_id: map_res!(digit, parse_num::<u8>) >>
+               char!(',') >>
+               text: call!(nom::rest) >>
+               (number_of_sentences, sentence_num, text_id, text)
+           ),
+           |data: (u8, u8, u8, &[u8])| -> Result<TxtData> {
+               Ok(TxtData {
+                   number_of_sentences: data.0,
+                   sentence_num: data.1,
+                   text_id: data.2,
+                   text: String::from(str::from_utf8(data.3).map_err(|_| ParseError::Nom)?),
+               })
+           }
+       )
+);
+
+/// Parse TXT
+/// from NMEA 4.0:
+/// $GPTXT,01,01,02,ANTSTATUS=OK*3B
+/// 1 Total number of sentences, 01 to 99
+/// 2 Sentence number, 01 to 99
+/// 3 Text identifier, u-blox and MediaTek use it as message type:
+/// 00 = Error, 01 = Warning, 02 = Notice, 07 = User
+/// 4 Text message
+///
+/// See `txt::TxtAssembler` to join multi-part messages.
+pub fn parse_txt(sentence: &NmeaSentence) -> Result<TxtData> {
+    if sentence.message_id != b"TXT" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_txt(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Geodetic datum, from the three-character code of DTM
+#[derive(Debug, PartialEq, Clone)]
+pub enum Datum {
+    Wgs84,
+    Wgs72,
+    Sgs85,
+    Pe90,
+    /// Datum defined by the user, code 999
+    UserDefined,
+    /// IHO datum code
+    Other(String),
+}
+
+fn parse_datum_code(data: &[u8]) -> Result<Option<Datum>> {
+    Ok(Some(match data {
+                b"" => return Ok(None),
+                b"W84" => Datum::Wgs84,
+                b"W72" => Datum::Wgs72,
+                b"S85" => Datum::Sgs85,
+                b"P90" => Datum::Pe90,
+                b"999" => Datum::UserDefined,
+                code => {
+                    Datum::Other(String::from(str::from_utf8(code).map_err(|_| ParseError::Nom)?))
+                }
+            }))
+}
+
+named!(datum_code<Option<Datum>>,
+       map_res!(alt_complete!(take_until!(",") | nom::rest), parse_datum_code));
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DtmData {
+    /// Datum of positions in all other sentences
+    pub local_datum: Datum,
+    pub subdivision: Option<String>,
+    /// Offset of local datum from reference datum, degrees, north positive
+    pub lat_offset: Option<f64>,
+    /// Offset of local datum from reference datum, degrees, east positive
+    pub lon_offset: Option<f64>,
+    /// Offset of local datum from reference datum, meters
+    pub alt_offset: Option<f32>,
+    /// `None` if empty, WGS84 is meant then
+    pub reference_datum: Option<Datum>,
+}
+
+impl DtmData {
+    /// Offsets to apply, `None` if positions are already in WGS84 or
+    /// the sender left the offsets empty
+    fn offsets(&self) -> Option<(f64, f64)> {
+        if self.local_datum == Datum::Wgs84 {
+            return None;
+        }
+        match (self.lat_offset, self.lon_offset) {
+            (Some(lat), Some(lon)) => Some((lat, lon)),
+            _ => None,
+        }
+    }
+
+    /// Datum of positions corrected with `correct_*` methods: reference datum
+    /// if the offsets could be applied, local datum otherwise
+    pub fn position_datum(&self) -> Datum {
+        match self.offsets() {
+            Some(_) => self.reference_datum.clone().unwrap_or(Datum::Wgs84),
+            None => self.local_datum.clone(),
+        }
+    }
+
+    /// Convert position in local datum to reference datum
+    pub fn correct_position(&self, latitude: f64, longitude: f64) -> (f64, f64) {
+        match self.offsets() {
+            Some((lat, lon)) => (latitude - lat, longitude - lon),
+            None => (latitude, longitude),
+        }
+    }
+
+    fn correct_altitude(&self, altitude: Option<f32>) -> Option<f32> {
+        match (self.offsets(), self.alt_offset) {
+            (Some(_), Some(offset)) => altitude.map(|v| v - offset),
+            _ => altitude,
+        }
+    }
+
+    fn correct_lat_lon(&self,
+                       latitude: &mut Option<f64>,
+                       longitude: &mut Option<f64>)
+                       -> Datum {
+        if let (Some(lat), Some(lon)) = (*latitude, *longitude) {
+            let (lat, lon) = self.correct_position(lat, lon);
+            *latitude = Some(lat);
+            *longitude = Some(lon);
+        }
+        self.position_datum()
+    }
+
+    /// Move GGA position to reference datum, returns datum of the position
+    pub fn correct_gga(&self, gga: &mut GgaData) -> Datum {
+        gga.altitude = self.correct_altitude(gga.altitude);
+        self.correct_lat_lon(&mut gga.latitude, &mut gga.longitude)
+    }
+
+    /// Move RMC position to reference datum, returns datum of the position
+    pub fn correct_rmc(&self, rmc: &mut RmcData) -> Datum {
+        self.correct_lat_lon(&mut rmc.lat, &mut rmc.lon)
+    }
+
+    /// Move GLL position to reference datum, returns datum of the position
+    pub fn correct_gll(&self, gll: &mut GllData) -> Datum {
+        self.correct_lat_lon(&mut gll.latitude, &mut gll.longitude)
+    }
+
+    /// Move GNS position to reference datum, returns datum of the position
+    pub fn correct_gns(&self, gns: &mut GnsData) -> Datum {
+        gns.altitude = self.correct_altitude(gns.altitude);
+        self.correct_lat_lon(&mut gns.latitude, &mut gns.longitude)
+    }
+}
+
+type DtmFields = (Option<Datum>, Option<String>, Option<f64>, Option<char>, Option<f64>,
+                  Option<char>, Option<f32>, Option<Datum>);
+
+named!(do_parse_dtm<DtmData>,
+       map_res!(
+           do_parse!(
+               local_datum: datum_code >>
+               char!(',') >>
+               subdivision: opt!(map!(map_res!(is_not!(","), str::from_utf8), String::from)) >>
+               char!(',') >>
+               lat_offset: opt!(map_res!(float_number, parse_float_num::<f64>)) >>
+               char!(',') >>
+               lat_dir: opt!(one_of!("NS")) >>
+               char!(',') >>
+               lon_offset: opt!(map_res!(float_number, parse_float_num::<f64>)) >>
+               char!(',') >>
+               lon_dir: opt!(one_of!("EW")) >>
+               char!(',') >>
+               alt_offset: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               reference_datum: datum_code >>
+               (local_datum, subdivision, lat_offset, lat_dir, lon_offset, lon_dir,
+                alt_offset, reference_datum)
+           ),
+           |data: DtmFields| -> Result<DtmData> {
+               let (lat_dir, lon_dir) = (data.3, data.5);
+               Ok(DtmData {
+                   local_datum: data.0.ok_or(ParseError::Nom)?,
+                   subdivision: data.1,
+                   lat_offset: data.2.map(|v| match lat_dir {
+                       Some('S') => -v / 60.,
+                       _ => v / 60.,
+                   }),
+                   lon_offset: data.4.map(|v| match lon_dir {
+                       Some('W') => -v / 60.,
+                       _ => v / 60.,
+                   }),
+                   alt_offset: data.6,
+                   reference_datum: data.7,
+               })
+           }
+       )
+);
+
+/// Parse DTM
+/// from NMEA 3.0:
+/// $GPDTM,W72,,0.00,S,0.01,W,-2.8,W84*4F
+/// 1 Local datum code: W84, W72, S85, P90, 999 = user defined or IHO code
+/// 2 Local datum subdivision code
+/// 3,4 Latitude offset, minutes
+/// 5,6 Longitude offset, minutes
+/// 7 Altitude offset, meters
+/// 8 Reference datum code
+///
+/// Positions in the local datum are the positions in the reference datum
+/// plus the offsets. See `DtmData::correct_gga` and friends to move
+/// positions back to the reference datum.
+pub fn parse_dtm(sentence: &NmeaSentence) -> Result<DtmData> {
+    if sentence.message_id != b"DTM" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_dtm(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// How GRS residuals were computed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GrsMode {
+    /// Residuals were used to calculate the position given in GGA or GNS
+    UsedInFix,
+    /// Residuals were recomputed after the position was computed
+    Recomputed,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GrsData {
+    pub fix_time: Option<NaiveTime>,
+    pub mode: GrsMode,
+    /// Range residuals, meters, in the order of satellites in the matching
+    /// GSA. `None` for empty fields.
+    pub residuals: Vec<Option<f32>>,
+    /// Constellation from system ID or talker id
+    pub gnss_type: Option<GnssType>,
+    /// GNSS system ID, NMEA 4.10 and later
+    pub system_id: Option<u8>,
+    /// GNSS signal ID, NMEA 4.10 and later
+    pub signal_id: Option<u8>,
+}
+
+impl GrsData {
+    /// Residuals paired with PRNs from GSA of the same epoch and constellation,
+    /// field by field, so empty GSA fields are skipped together with their
+    /// residuals. Empty if GSA belongs to another constellation.
+    pub fn satellite_residuals(&self, gsa: &GsaData) -> Vec<(u32, f32)> {
+        if let (Some(a), Some(b)) = (&self.gnss_type, &gsa.gnss_type) {
+            if a != b {
+                return Vec::new();
+            }
+        }
+        gsa.fix_sats_slots
+            .iter()
+            .zip(self.residuals.iter())
+            .filter_map(|(prn, residual)| prn.and_then(|prn| residual.map(|v| (prn, v))))
+            .collect()
+    }
+}
+
+named!(do_parse_grs<GrsData>,
+       do_parse!(
+           fix_time: opt!(complete!(parse_hms)) >>
+           char!(',') >>
+           mode: one_of!("01") >>
+           // always 12 residual fields, empty ones for unused channels
+           residuals: count!(preceded!(
+               char!(','),
+               opt!(complete!(map_res!(signed_number, parse_float_num::<f32>)))
+           ), 12) >>
+           system_id: opt!(complete!(preceded!(char!(','), map_res!(take!(1), parse_hex)))) >>
+           signal_id: opt!(complete!(preceded!(char!(','), map_res!(take!(1), parse_hex)))) >>
+           (GrsData {
+               fix_time,
+               mode: if mode == '0' { GrsMode::UsedInFix } else { GrsMode::Recomputed },
+               residuals,
+               gnss_type: None,
+               system_id,
+               signal_id,
+           })
+       )
+);
+
+/// Parse GRS
+/// from NMEA 4.10:
+/// $GPGRS,220320.0,0,-0.8,-0.2,-0.1,-0.2,0.8,0.6,,,,,,*79
+/// 1 UTC time of the GGA or GNS fix associated with this sentence
+/// 2 Mode: 0 = residuals were used to calculate the position,
+/// 1 = residuals were recomputed after the position was computed
+/// 3-14 Range residuals of satellites used in the solution, meters,
+/// in the order of PRNs in GSA
+/// 15 GNSS system ID (NMEA 4.10 and later)
+/// 16 GNSS signal ID (NMEA 4.10 and later)
+///
+/// See `grs::ResidualStatistics` to collect residuals per satellite.
+pub fn parse_grs(sentence: &NmeaSentence) -> Result<GrsData> {
+    if sentence.message_id != b"GRS" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    let mut ret = do_parse_grs(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })?;
+    ret.gnss_type = match ret.system_id {
+        Some(id) => GnssType::from_system_id(id),
+        None => GnssType::from_talker_id(sentence.talker_id),
+    };
+    Ok(ret)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RotData {
+    /// Rate of turn, degrees per minute, negative when bow turns to port
+    pub rate_of_turn: Option<f32>,
+    /// Status: A = data valid, V = data invalid
+    pub valid: bool,
+}
+
+named!(do_parse_rot<RotData>,
+       do_parse!(
+           rate_of_turn: opt!(map_res!(complete!(signed_number), parse_float_num::<f32>)) >>
+           char!(',') >>
+           status: opt!(complete!(one_of!("AV"))) >>
+           (RotData {
+               rate_of_turn,
+               valid: status == Some('A'),
+           })
+       )
+);
+
+/// Parse ROT
+/// from gpsd:
+/// $HEROT,-11.23,A*07
+/// 1 Rate of turn, degrees per minute, "-" means bow turns to port
+/// 2 Status: A = data valid, V = data invalid
+pub fn parse_rot(sentence: &NmeaSentence) -> Result<RotData> {
+    if sentence.message_id != b"ROT" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_rot(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RsaData {
+    /// Starboard rudder angle, or the only one for single rudder, degrees,
+    /// negative when bow turns to port
+    pub starboard: Option<f32>,
+    /// Status of starboard rudder sensor: A = data valid, V = data invalid
+    pub starboard_valid: bool,
+    /// Port rudder angle, degrees, negative when bow turns to port
+    pub port: Option<f32>,
+    /// Status of port rudder sensor: A = data valid, V = data invalid
+    pub port_valid: bool,
+}
+
+named!(do_parse_rsa<RsaData>,
+       do_parse!(
+           starboard: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           starboard_status: opt!(one_of!("AV")) >>
+           char!(',') >>
+           port: opt!(map_res!(complete!(signed_number), parse_float_num::<f32>)) >>
+           char!(',') >>
+           port_status: opt!(complete!(one_of!("AV"))) >>
+           (RsaData {
+               starboard,
+               starboard_valid: starboard_status == Some('A'),
+               port,
+               port_valid: port_status == Some('A'),
+           })
+       )
+);
+
+/// Parse RSA
+/// from gpsd:
+/// $IIRSA,10.5,A,,V*4D
+/// 1 Starboard (or single) rudder sensor, "-" means turn to port
+/// 2 Status: A = data valid, V = data invalid
+/// 3 Port rudder sensor
+/// 4 Status: A = data valid, V = data invalid
+pub fn parse_rsa(sentence: &NmeaSentence) -> Result<RsaData> {
+    if sentence.message_id != b"RSA" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_rsa(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Transducer type of XDR measurement
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TransducerType {
+    /// A, angular displacement, such as pitch and roll
+    Angular,
+    /// C
+    Temperature,
+    /// D
+    Linear,
+    /// F
+    Frequency,
+    /// G
+    Generic,
+    /// H
+    Humidity,
+    /// I
+    Current,
+    /// L
+    Salinity,
+    /// N
+    Force,
+    /// P
+    Pressure,
+    /// R
+    FlowRate,
+    /// S
+    Switch,
+    /// T
+    Tachometer,
+    /// U
+    Voltage,
+    /// V
+    Volume,
+    Other(char),
+}
+
+impl From<char> for TransducerType {
+    fn from(x: char) -> Self {
+        match x {
+            'A' => TransducerType::Angular,
+            'C' => TransducerType::Temperature,
+            'D' => TransducerType::Linear,
+            'F' => TransducerType::Frequency,
+            'G' => TransducerType::Generic,
+            'H' => TransducerType::Humidity,
+            'I' => TransducerType::Current,
+            'L' => TransducerType::Salinity,
+            'N' => TransducerType::Force,
+            'P' => TransducerType::Pressure,
+            'R' => TransducerType::FlowRate,
+            'S' => TransducerType::Switch,
+            'T' => TransducerType::Tachometer,
+            'U' => TransducerType::Voltage,
+            'V' => TransducerType::Volume,
+            x => TransducerType::Other(x),
+        }
+    }
+}
+
+/// Unit of XDR measurement. The unit letter means different units for
+/// different transducer types, for example P is pascals for pressure and
+/// percent for humidity.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TransducerUnit {
+    Degrees,
+    Celsius,
+    Meters,
+    Hertz,
+    Percent,
+    Amperes,
+    PartsPerThousand,
+    Newtons,
+    Bars,
+    Pascals,
+    LitersPerSecond,
+    Rpm,
+    Volts,
+    CubicMeters,
+    Other(char),
+}
+
+impl TransducerUnit {
+    fn new(transducer_type: TransducerType, unit: char) -> TransducerUnit {
+        match (transducer_type, unit) {
+            (TransducerType::Angular, 'D') => TransducerUnit::Degrees,
+            (TransducerType::Temperature, 'C') => TransducerUnit::Celsius,
+            (TransducerType::Linear, 'M') => TransducerUnit::Meters,
+            (TransducerType::Frequency, 'H') => TransducerUnit::Hertz,
+            (TransducerType::Humidity, 'P') => TransducerUnit::Percent,
+            (TransducerType::Current, 'A') => TransducerUnit::Amperes,
+            (TransducerType::Salinity, 'S') => TransducerUnit::PartsPerThousand,
+            (TransducerType::Force, 'N') => TransducerUnit::Newtons,
+            (TransducerType::Pressure, 'B') => TransducerUnit::Bars,
+            (TransducerType::Pressure, 'P') => TransducerUnit::Pascals,
+            (TransducerType::FlowRate, 'L') => TransducerUnit::LitersPerSecond,
+            (TransducerType::Tachometer, 'R') => TransducerUnit::Rpm,
+            (TransducerType::Voltage, 'V') => TransducerUnit::Volts,
+            (TransducerType::Volume, 'M') => TransducerUnit::CubicMeters,
+            (_, x) => TransducerUnit::Other(x),
+        }
+    }
+}
+
+/// One type, value, unit and name quadruplet of XDR
+#[derive(Debug, PartialEq, Clone)]
+pub struct XdrMeasurement {
+    pub transducer_type: TransducerType,
+    pub value: Option<f32>,
+    pub unit: Option<TransducerUnit>,
+    /// Transducer name, chosen by the sender
+    pub name: Option<String>,
+}
+
+/// Well-known XDR measurements, see `XdrData::get`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum XdrQuantity {
+    /// Degrees, bow up positive
+    Pitch,
+    /// Degrees, starboard down positive
+    Roll,
+    /// Bars
+    BarometricPressure,
+    /// Degrees Celsius
+    AirTemperature,
+    /// Percent
+    Humidity,
+    /// Volts
+    BatteryVoltage,
+}
+
+impl XdrQuantity {
+    fn transducer_type(&self) -> TransducerType {
+        match *self {
+            XdrQuantity::Pitch | XdrQuantity::Roll => TransducerType::Angular,
+            XdrQuantity::BarometricPressure => TransducerType::Pressure,
+            XdrQuantity::AirTemperature => TransducerType::Temperature,
+            XdrQuantity::Humidity => TransducerType::Humidity,
+            XdrQuantity::BatteryVoltage => TransducerType::Voltage,
+        }
+    }
+
+    /// Transducer names used by common senders, compared ignoring case
+    fn names(&self) -> &'static [&'static str] {
+        match *self {
+            XdrQuantity::Pitch => &["PTCH", "PITCH"],
+            XdrQuantity::Roll => &["ROLL"],
+            XdrQuantity::BarometricPressure => &["Barometer", "BARO"],
+            XdrQuantity::AirTemperature => &["AirTemp", "TempAir", "ENV_OUTAIR_T"],
+            XdrQuantity::Humidity => &["Humidity", "ENV_OUTSIDE_H"],
+            XdrQuantity::BatteryVoltage => &["Battery", "BATT"],
+        }
+    }
+
+    /// Convert value to the unit of the quantity, `None` for unexpected units
+    fn convert(&self, value: f32, unit: TransducerUnit) -> Option<f32> {
+        match (*self, unit) {
+            (XdrQuantity::Pitch, TransducerUnit::Degrees) |
+            (XdrQuantity::Roll, TransducerUnit::Degrees) |
+            (XdrQuantity::BarometricPressure, TransducerUnit::Bars) |
+            (XdrQuantity::AirTemperature, TransducerUnit::Celsius) |
+            (XdrQuantity::Humidity, TransducerUnit::Percent) |
+            (XdrQuantity::BatteryVoltage, TransducerUnit::Volts) => Some(value),
+            (XdrQuantity::BarometricPressure, TransducerUnit::Pascals) => Some(value / 100_000.),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct XdrData {
+    pub measurements: Vec<XdrMeasurement>,
+}
+
+impl XdrData {
+    /// First measurement with the name
+    pub fn measurement(&self, name: &str) -> Option<&XdrMeasurement> {
+        self.measurements
+            .iter()
+            .find(|v| v.name.as_deref() == Some(name))
+    }
+
+    /// Measurements of one transducer type
+    pub fn measurements_of<'a>(&'a self,
+                               transducer_type: TransducerType)
+                               -> impl Iterator<Item = &'a XdrMeasurement> + 'a {
+        self.measurements
+            .iter()
+            .filter(move |v| v.transducer_type == transducer_type)
+    }
+
+    /// Value of well-known measurement, found by transducer type and name,
+    /// in the unit of the quantity
+    pub fn get(&self, quantity: XdrQuantity) -> Option<f32> {
+        let transducer_type = quantity.transducer_type();
+        let names = quantity.names();
+        self.measurements
+            .iter()
+            .filter(|v| v.transducer_type == transducer_type)
+            .filter(|v| match v.name {
+                        Some(ref name) => names.iter().any(|v| v.eq_ignore_ascii_case(name)),
+                        None => false,
+                    })
+            .filter_map(|v| match (v.value, v.unit) {
+                            (Some(value), Some(unit)) => quantity.convert(value, unit),
+                            _ => None,
+                        })
+            .next()
+    }
+}
+
+named!(xdr_measurement<XdrMeasurement>,
+       do_parse!(
+           transducer_type: none_of!(",") >>
+           char!(',') >>
+           value: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           unit: opt!(none_of!(",")) >>
+           char!(',') >>
+           name: map_res!(alt_complete!(take_until!(",") | nom::rest), parse_text_field) >>
+           (XdrMeasurement {
+               transducer_type: TransducerType::from(transducer_type),
+               value,
+               unit: unit.map(|v| TransducerUnit::new(TransducerType::from(transducer_type), v)),
+               name,
+           })
+       )
+);
+
+named!(do_parse_xdr<XdrData>,
+       do_parse!(
+           first: xdr_measurement >>
+           rest: many0!(complete!(preceded!(char!(','), xdr_measurement))) >>
+           (XdrData {
+               measurements: {
+                   let mut measurements = rest;
+                   measurements.insert(0, first);
+                   measurements
+               },
+           })
+       )
+);
+
+/// Parse XDR
+/// from gpsd:
+/// $HCXDR,A,171,D,PITCH,A,-37,D,ROLL,G,367,,MAGX,G,2420,,MAGY,G,-8984,,MAGZ*41
+/// Repeating quadruplets of:
+/// 1 Transducer type
+/// 2 Measurement data
+/// 3 Units of measurement
+/// 4 Name of transducer
+///
+/// See `XdrData::get` for well-known measurements.
+pub fn parse_xdr(sentence: &NmeaSentence) -> Result<XdrData> {
+    if sentence.message_id != b"XDR" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_xdr(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MtwData {
+    /// Water temperature, degrees Celsius
+    pub temperature: Option<f32>,
+}
+
+named!(do_parse_mtw<MtwData>,
+       do_parse!(
+           temperature: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           opt!(complete!(char!('C'))) >>
+           (MtwData { temperature })
+       )
+);
+
+/// Parse MTW
+/// from gpsd:
+/// $YXMTW,17.75,C*26
+/// 1 Water temperature
+/// 2 Unit of measurement, C = degrees Celsius
+pub fn parse_mtw(sentence: &NmeaSentence) -> Result<MtwData> {
+    if sentence.message_id != b"MTW" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_mtw(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MdaData {
+    /// Barometric pressure, inches of mercury
+    pub pressure_inches: Option<f32>,
+    /// Barometric pressure, bars
+    pub pressure_bars: Option<f32>,
+    /// Air temperature, degrees Celsius
+    pub air_temperature: Option<f32>,
+    /// Water temperature, degrees Celsius
+    pub water_temperature: Option<f32>,
+    /// Relative humidity, percent
+    pub relative_humidity: Option<f32>,
+    /// Absolute humidity, percent
+    pub absolute_humidity: Option<f32>,
+    /// Dew point, degrees Celsius
+    pub dew_point: Option<f32>,
+    /// Wind direction, degrees true
+    pub wind_direction_true: Option<f32>,
+    /// Wind direction, degrees magnetic
+    pub wind_direction_magnetic: Option<f32>,
+    /// Wind speed, knots
+    pub wind_speed_knots: Option<f32>,
+    /// Wind speed, meters per second
+    pub wind_speed_mps: Option<f32>,
+}
+
+named!(mda_value<Option<f32>>, opt!(map_res!(signed_number, parse_float_num::<f32>)));
+
+named!(do_parse_mda<MdaData>,
+       do_parse!(
+           pressure_inches: mda_value >>
+           char!(',') >>
+           opt!(char!('I')) >>
+           char!(',') >>
+           pressure_bars: mda_value >>
+           char!(',') >>
+           opt!(char!('B')) >>
+           char!(',') >>
+           air_temperature: mda_value >>
+           char!(',') >>
+           opt!(char!('C')) >>
+           char!(',') >>
+           water_temperature: mda_value >>
+           char!(',') >>
+           opt!(char!('C')) >>
+           char!(',') >>
+           relative_humidity: mda_value >>
+           char!(',') >>
+           absolute_humidity: mda_value >>
+           char!(',') >>
+           dew_point: mda_value >>
+           char!(',') >>
+           opt!(char!('C')) >>
+           char!(',') >>
+           wind_direction_true: mda_value >>
+           char!(',') >>
+           opt!(char!('T')) >>
+           char!(',') >>
+           wind_direction_magnetic: mda_value >>
+           char!(',') >>
+           opt!(char!('M')) >>
+           char!(',') >>
+           wind_speed_knots: mda_value >>
+           char!(',') >>
+           opt!(char!('N')) >>
+           char!(',') >>
+           wind_speed_mps: mda_value >>
+           char!(',') >>
+           opt!(complete!(char!('M'))) >>
+           (MdaData {
+               pressure_inches,
+               pressure_bars,
+               air_temperature,
+               water_temperature,
+               relative_humidity,
+               absolute_humidity,
+               dew_point,
+               wind_direction_true,
+               wind_direction_magnetic,
+               wind_speed_knots,
+               wind_speed_mps,
+           })
+       )
+);
+
+/// Parse MDA
+/// from NMEA 3.0:
+/// $WIMDA,30.2269,I,1.0236,B,17.7,C,,,43.3,,5.0,C,131.5,T,134.6,M,0.8,N,0.4,M*59
+/// 1,2 Barometric pressure, inches of mercury
+/// 3,4 Barometric pressure, bars
+/// 5,6 Air temperature, degrees Celsius
+/// 7,8 Water temperature, degrees Celsius
+/// 9 Relative humidity, percent
+/// 10 Absolute humidity, percent
+/// 11,12 Dew point, degrees Celsius
+/// 13,14 Wind direction, degrees true
+/// 15,16 Wind direction, degrees magnetic
+/// 17,18 Wind speed, knots
+/// 19,20 Wind speed, meters per second
+pub fn parse_mda(sentence: &NmeaSentence) -> Result<MdaData> {
+    if sentence.message_id != b"MDA" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_mda(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Tracking status of radar target
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TargetStatus {
+    /// L, target was lost
+    Lost,
+    /// Q, target is being acquired
+    Acquiring,
+    /// T
+    Tracking,
+}
+
+/// How radar target was acquired
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TargetAcquisition {
+    Automatic,
+    Manual,
+    /// Target position reported by other means, such as AIS
+    Reported,
+}
+
+fn parse_target_status(status: char) -> TargetStatus {
+    match status {
+        'L' => TargetStatus::Lost,
+        'Q' => TargetStatus::Acquiring,
+        _ => TargetStatus::Tracking,
+    }
+}
+
+/// Convert distance to nautical miles, `unit` is TTM unit letter:
+/// N = nautical miles, K = kilometers, S = statute miles
+fn distance_to_nm(distance: f32, unit: char) -> Option<f32> {
+    match unit {
+        'N' => Some(distance),
+        'K' => Some(distance / 1.852),
+        'S' => Some(distance * 1609.344 / 1852.),
+        _ => None,
+    }
+}
+
+named!(target_bearing<Option<Bearing>>,
+       do_parse!(
+           angle: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+           char!(',') >>
+           reference: opt!(one_of!("TR")) >>
+           (angle.map(|v| if reference == Some('R') { Bearing::Relative(v) } else { Bearing::True(v) }))
+       )
+);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TtmData {
+    pub target_number: u8,
+    /// Distance from own ship, nautical miles
+    pub distance: Option<f32>,
+    /// Bearing from own ship
+    pub bearing: Option<Bearing>,
+    /// Target speed, knots
+    pub speed: Option<f32>,
+    pub course: Option<Bearing>,
+    /// Distance of closest point of approach, nautical miles
+    pub cpa_distance: Option<f32>,
+    /// Time until closest point of approach, minutes,
+    /// negative if it is already passed
+    pub cpa_time: Option<f32>,
+    pub name: Option<String>,
+    pub status: Option<TargetStatus>,
+    /// Target is used as reference to determine own ship position or speed
+    pub reference_target: bool,
+    /// UTC time of data, NMEA 3.0 and later
+    pub fix_time: Option<NaiveTime>,
+    /// NMEA 3.0 and later
+    pub acquisition: Option<TargetAcquisition>,
+}
+
+named!(do_parse_ttm<TtmData>,
+       map_res!(
+           do_parse!(
+               target_number: map_res!(digit, parse_num::<u8>) >>
+               char!(',') >>
+               distance: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               bearing: target_bearing >>
+               char!(',') >>
+               speed: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               course: target_bearing >>
+               char!(',') >>
+               cpa_distance: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               cpa_time: opt!(map_res!(signed_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               unit: opt!(one_of!("KNS")) >>
+               char!(',') >>
+               name: map_res!(take_until!(","), parse_text_field) >>
+               char!(',') >>
+               status: opt!(one_of!("LQT")) >>
+               char!(',') >>
+               reference: opt!(complete!(char!('R'))) >>
+               fix_time: opt!(complete!(preceded!(char!(','), opt!(complete!(parse_hms))))) >>
+               acquisition: opt!(complete!(preceded!(char!(','), one_of!("AMR")))) >>
+               (TtmData {
+                   target_number,
+                   distance,
+                   bearing,
+                   speed,
+                   course,
+                   cpa_distance,
+                   cpa_time,
+                   name,
+                   status: status.map(parse_target_status),
+                   reference_target: reference.is_some(),
+                   fix_time: fix_time.and_then(|v| v),
+                   acquisition: acquisition.map(|v| match v {
+                       'A' => TargetAcquisition::Automatic,
+                       'M' => TargetAcquisition::Manual,
+                       _ => TargetAcquisition::Reported,
+                   }),
+               }, unit)
+           ),
+           |data: (TtmData, Option<char>)| -> Result<TtmData> {
+               let (ttm, unit) = data;
+               let unit = unit.unwrap_or('N');
+               let distance = |v: Option<f32>| -> Result<Option<f32>> {
+                   match v {
+                       Some(v) => distance_to_nm(v, unit).map(Some).ok_or(ParseError::Nom),
+                       None => Ok(None),
+                   }
+               };
+               Ok(TtmData {
+                   distance: distance(ttm.distance)?,
+                   cpa_distance: distance(ttm.cpa_distance)?,
+                   speed: match ttm.speed {
+                       Some(v) => Some(speed_to_knots(v, unit).ok_or(ParseError::Nom)?),
+                       None => None,
+                   },
+                   ..ttm
+               })
+           }
+       )
+);
+
+/// Parse TTM
+/// from NMEA 3.0:
+/// $RATTM,11,25.3,13.7,T,7.0,20.0,T,10.1,-20.0,N,THEM,T,,110025.00,A*0F
+/// 1 Target number, 00 to 99
+/// 2 Target distance from own ship
+/// 3,4 Bearing from own ship, T = true, R = relative
+/// 5 Target speed
+/// 6,7 Target course, T = true, R = relative
+/// 8 Distance of closest point of approach
+/// 9 Time to CPA, minutes, "-" means increasing
+/// 10 Speed and distance units: K = km/h and km, N = knots and nautical
+/// miles, S = statute miles per hour and statute miles
+/// 11 Target name
+/// 12 Target status: L = lost, Q = acquiring, T = tracking
+/// 13 Reference target: R if used as reference, empty otherwise
+/// 14 UTC time of data (NMEA 3.0 and later)
+/// 15 Type of acquisition: A = automatic, M = manual, R = reported
+/// (NMEA 3.0 and later)
+///
+/// Distances are converted to nautical miles and speed to knots.
+/// See `target::TargetTable` to keep track of targets.
+pub fn parse_ttm(sentence: &NmeaSentence) -> Result<TtmData> {
+    if sentence.message_id != b"TTM" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_ttm(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TllData {
+    pub target_number: u8,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub name: Option<String>,
+    pub fix_time: Option<NaiveTime>,
+    pub status: Option<TargetStatus>,
+    /// Target is used as reference to determine own ship position or speed
+    pub reference_target: bool,
+}
+
+named!(do_parse_tll<TllData>,
+       do_parse!(
+           target_number: map_res!(digit, parse_num::<u8>) >>
+           char!(',') >>
+           lat_lon: parse_lat_lon >>
+           char!(',') >>
+           name: map_res!(take_until!(","), parse_text_field) >>
+           char!(',') >>
+           fix_time: opt!(parse_hms) >>
+           char!(',') >>
+           status: opt!(one_of!("LQT")) >>
+           reference: opt!(complete!(preceded!(char!(','), opt!(complete!(char!('R')))))) >>
+           (TllData {
+               target_number,
+               latitude: lat_lon.map(|v| v.0),
+               longitude: lat_lon.map(|v| v.1),
+               name,
+               fix_time,
+               status: status.map(parse_target_status),
+               reference_target: reference.and_then(|v| v).is_some(),
+           })
+       )
+);
+
+/// Parse TLL
+/// from NMEA 3.0:
+/// $RATLL,01,5603.370,N,00948.347,E,ALPHA,015200.36,T,*7D
+/// 1 Target number, 00 to 99
+/// 2,3 Target latitude
+/// 4,5 Target longitude
+/// 6 Target name
+/// 7 UTC time of data
+/// 8 Target status: L = lost, Q = acquiring, T = tracking
+/// 9 Reference target: R if used as reference, empty otherwise
+pub fn parse_tll(sentence: &NmeaSentence) -> Result<TllData> {
+    if sentence.message_id != b"TLL" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_tll(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
+/// Reference system of own ship course and speed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SpeedReference {
+    /// B, bottom tracking log
+    BottomTrack,
+    /// M, manually entered
+    Manual,
+    /// W, water referenced
+    Water,
+    /// R, radar tracking of fixed target
+    Radar,
+    /// P, positioning system ground reference
+    Positioning,
+}
+
+named!(speed_reference<SpeedReference>,
+       map!(one_of!("BMWRP"), |v| match v {
+           'B' => SpeedReference::BottomTrack,
+           'M' => SpeedReference::Manual,
+           'W' => SpeedReference::Water,
+           'R' => SpeedReference::Radar,
+           _ => SpeedReference::Positioning,
+       })
+);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OsdData {
+    /// Heading, degrees true
+    pub heading: Option<f32>,
+    /// Heading status: A = data valid, V = data invalid
+    pub heading_valid: bool,
+    /// Course, degrees true
+    pub course: Option<f32>,
+    pub course_reference: Option<SpeedReference>,
+    /// Speed, knots
+    pub speed: Option<f32>,
+    pub speed_reference: Option<SpeedReference>,
+    /// Set, direction of current, degrees true
+    pub set: Option<f32>,
+    /// Drift, speed of current, knots
+    pub drift: Option<f32>,
+}
+
+named!(do_parse_osd<OsdData>,
+       map_res!(
+           do_parse!(
+               heading: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               heading_status: opt!(one_of!("AV")) >>
+               char!(',') >>
+               course: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               course_reference: opt!(speed_reference) >>
+               char!(',') >>
+               speed: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               speed_reference: opt!(speed_reference) >>
+               char!(',') >>
+               set: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               drift: opt!(map_res!(float_number, parse_float_num::<f32>)) >>
+               char!(',') >>
+               unit: opt!(complete!(one_of!("KNS"))) >>
+               (OsdData {
+                   heading,
+                   heading_valid: heading_status == Some('A'),
+                   course,
+                   course_reference,
+                   speed,
+                   speed_reference,
+                   set,
+                   drift,
+               }, unit)
+           ),
+           |data: (OsdData, Option<char>)| -> Result<OsdData> {
+               let (osd, unit) = data;
+               let unit = unit.unwrap_or('N');
+               let knots = |v: Option<f32>| -> Result<Option<f32>> {
+                   match v {
+                       Some(v) => speed_to_knots(v, unit).map(Some).ok_or(ParseError::Nom),
+                       None => Ok(None),
+                   }
+               };
+               Ok(OsdData {
+                   speed: knots(osd.speed)?,
+                   drift: knots(osd.drift)?,
+                   ..osd
+               })
+           }
+       )
+);
+
+/// Parse OSD
+/// from gpsd:
+/// $RAOSD,35.1,A,36.0,P,10.2,P,15.3,0.1,N*41
+/// 1 Heading, degrees true
+/// 2 Heading status: A = data valid, V = data invalid
+/// 3 Vessel course, degrees true
+/// 4 Course reference: B = bottom tracking log, M = manually entered,
+/// W = water referenced, R = radar tracking of fixed target,
+/// P = positioning system ground reference
+/// 5 Vessel speed
+/// 6 Speed reference, as course reference
+/// 7 Vessel set, degrees true
+/// 8 Vessel drift (speed)
+/// 9 Speed units: K = km/h, N = knots, S = statute miles per hour
+///
+/// Speed and drift are converted to knots.
+pub fn parse_osd(sentence: &NmeaSentence) -> Result<OsdData> {
+    if sentence.message_id != b"OSD" {
+        Err(ParseError::InvalidMessageId)?
+    }
+    do_parse_osd(sentence.data)
+        .map(|(_, o)| o)
+        .map_err(|err| match err {
+                     Err::Incomplete(_) => ParseError::Incomplete,
+                     _ => ParseError::Nom,
+                 })
+}
+
 #[derive(Debug)]
 pub enum ParseResult<'a> {
     GGA(GgaData),
     RMC(RmcData),
     GSA(GsaData),
     VTG(VtgData),
+    GSV(GsvData),
+    GLL(GllData),
+    ZDA(ZdaData),
+    GST(GstData),
+    GNS(GnsData),
+    GBS(GbsData),
+    HDT(HdtData),
+    HDM(HdmData),
+    HDG(HdgData),
+    THS(ThsData),
+    MWV(MwvData),
+    MWD(MwdData),
+    VWR(VwrData),
+    DBT(DbtData),
+    DBS(DbsData),
+    DPT(DptData),
+    VHW(VhwData),
+    VBW(VbwData),
+    VLW(VlwData),
+    XTE(XteData),
+    APB(ApbData),
+    RMB(RmbData),
+    BWC(BwcData),
+    BOD(BodData),
+    WPL(WplData),
+    RTE(RteData),
+    VDM(VdmData),
+    VDO(VdmData),
+    TXT(TxtData),
+    DTM(DtmData),
+    GRS(GrsData),
+    ROT(RotData),
+    RSA(RsaData),
+    XDR(XdrData),
+    MTW(MtwData),
+    MDA(MdaData),
+    TTM(TtmData),
+    TLL(TllData),
+    OSD(OsdData),
     Unsupported(&'a [u8]),
 }
 
 /// parse nmea 0183 sentence and extract data from it
-pub fn parse(xs: &[u8]) -> Result<ParseResult> {
+pub fn parse(xs: &[u8]) -> Result<ParseResult<'_>> {
     let nmea_sentence = parse_nmea_sentence(xs)?;
 
     if nmea_sentence.checksum == nmea_sentence.calc_checksum() {
@@ -757,6 +3934,45 @@ pub fn parse(xs: &[u8]) -> Result<ParseResult> {
             }
             x if x == b"GSA" => Ok(ParseResult::GSA(parse_gsa(&nmea_sentence)?)),
             x if x == b"VTG" => Ok(ParseResult::VTG(parse_vtg(&nmea_sentence)?)),
+            x if x == b"GSV" => Ok(ParseResult::GSV(parse_gsv(&nmea_sentence)?)),
+            x if x == b"GLL" => Ok(ParseResult::GLL(parse_gll(&nmea_sentence)?)),
+            x if x == b"ZDA" => Ok(ParseResult::ZDA(parse_zda(&nmea_sentence)?)),
+            x if x == b"GST" => Ok(ParseResult::GST(parse_gst(&nmea_sentence)?)),
+            x if x == b"GNS" => Ok(ParseResult::GNS(parse_gns(&nmea_sentence)?)),
+            x if x == b"GBS" => Ok(ParseResult::GBS(parse_gbs(&nmea_sentence)?)),
+            x if x == b"HDT" => Ok(ParseResult::HDT(parse_hdt(&nmea_sentence)?)),
+            x if x == b"HDM" => Ok(ParseResult::HDM(parse_hdm(&nmea_sentence)?)),
+            x if x == b"HDG" => Ok(ParseResult::HDG(parse_hdg(&nmea_sentence)?)),
+            x if x == b"THS" => Ok(ParseResult::THS(parse_ths(&nmea_sentence)?)),
+            x if x == b"MWV" => Ok(ParseResult::MWV(parse_mwv(&nmea_sentence)?)),
+            x if x == b"MWD" => Ok(ParseResult::MWD(parse_mwd(&nmea_sentence)?)),
+            x if x == b"VWR" => Ok(ParseResult::VWR(parse_vwr(&nmea_sentence)?)),
+            x if x == b"DBT" => Ok(ParseResult::DBT(parse_dbt(&nmea_sentence)?)),
+            x if x == b"DBS" => Ok(ParseResult::DBS(parse_dbs(&nmea_sentence)?)),
+            x if x == b"DPT" => Ok(ParseResult::DPT(parse_dpt(&nmea_sentence)?)),
+            x if x == b"VHW" => Ok(ParseResult::VHW(parse_vhw(&nmea_sentence)?)),
+            x if x == b"VBW" => Ok(ParseResult::VBW(parse_vbw(&nmea_sentence)?)),
+            x if x == b"VLW" => Ok(ParseResult::VLW(parse_vlw(&nmea_sentence)?)),
+            x if x == b"XTE" => Ok(ParseResult::XTE(parse_xte(&nmea_sentence)?)),
+            x if x == b"APB" => Ok(ParseResult::APB(parse_apb(&nmea_sentence)?)),
+            x if x == b"RMB" => Ok(ParseResult::RMB(parse_rmb(&nmea_sentence)?)),
+            x if x == b"BWC" => Ok(ParseResult::BWC(parse_bwc(&nmea_sentence)?)),
+            x if x == b"BOD" => Ok(ParseResult::BOD(parse_bod(&nmea_sentence)?)),
+            x if x == b"WPL" => Ok(ParseResult::WPL(parse_wpl(&nmea_sentence)?)),
+            x if x == b"RTE" => Ok(ParseResult::RTE(parse_rte(&nmea_sentence)?)),
+            x if x == b"VDM" => Ok(ParseResult::VDM(parse_vdm(&nmea_sentence)?)),
+            x if x == b"VDO" => Ok(ParseResult::VDO(parse_vdm(&nmea_sentence)?)),
+            x if x == b"TXT" => Ok(ParseResult::TXT(parse_txt(&nmea_sentence)?)),
+            x if x == b"DTM" => Ok(ParseResult::DTM(parse_dtm(&nmea_sentence)?)),
+            x if x == b"GRS" => Ok(ParseResult::GRS(parse_grs(&nmea_sentence)?)),
+            x if x == b"ROT" => Ok(ParseResult::ROT(parse_rot(&nmea_sentence)?)),
+            x if x == b"RSA" => Ok(ParseResult::RSA(parse_rsa(&nmea_sentence)?)),
+            x if x == b"XDR" => Ok(ParseResult::XDR(parse_xdr(&nmea_sentence)?)),
+            x if x == b"MTW" => Ok(ParseResult::MTW(parse_mtw(&nmea_sentence)?)),
+            x if x == b"MDA" => Ok(ParseResult::MDA(parse_mda(&nmea_sentence)?)),
+            x if x == b"TTM" => Ok(ParseResult::TTM(parse_ttm(&nmea_sentence)?)),
+            x if x == b"TLL" => Ok(ParseResult::TLL(parse_tll(&nmea_sentence)?)),
+            x if x == b"OSD" => Ok(ParseResult::OSD(parse_osd(&nmea_sentence)?)),
             x => {
                 Ok(ParseResult::Unsupported(x))
             }
@@ -0,0 +1,87 @@
+//! Mapping of NMEA satellite numbers (PRN) onto constellations.
+//!
+//! NMEA numbering, from gpsd and NMEA 4.11:
+//!
+//! ```text
+//!   1 - 32    GPS
+//!  33 - 64    SBAS (SBAS PRN 120 - 151)
+//!  65 - 96    GLONASS (slot number 1 - 32)
+//! 152 - 158   SBAS (u-blox extended numbering)
+//! 193 - 202   QZSS (NMEA 4.11)
+//! 203 - 263   BeiDou (u-blox, not NMEA, 201 and 202 are taken by QZSS)
+//! 301 - 336   Galileo
+//! 401 - 463   BeiDou (NMEA 4.11)
+//! ```
+//!
+//! Since NMEA 4.10 receivers use constellation talker ids (GA, GB, QZ, GI)
+//! or add system ID to GSA and GRS sentences. In that case satellites are
+//! numbered inside of the constellation: Galileo 1 - 36, BeiDou 1 - 63,
+//! QZSS 1 - 10, NavIC 1 - 14.
+
+use GnssType;
+
+/// Constellation and native satellite number from NMEA PRN, without any
+/// talker or system ID hint
+///
+/// Native number is PRN for GPS, SBAS and QZSS, slot number for GLONASS
+/// and satellite ID for Galileo, BeiDou and NavIC.
+pub fn from_nmea_prn(prn: u32) -> Option<(GnssType, u32)> {
+    match prn {
+        1..=32 => Some((GnssType::Gps, prn)),
+        33..=64 => Some((GnssType::Sbas, prn + 87)),
+        65..=96 => Some((GnssType::Glonass, prn - 64)),
+        152..=158 => Some((GnssType::Sbas, prn)),
+        193..=202 => Some((GnssType::Qzss, prn)),
+        203..=263 => Some((GnssType::Beidou, prn - 200)),
+        301..=336 => Some((GnssType::Galileo, prn - 300)),
+        401..=463 => Some((GnssType::Beidou, prn - 400)),
+        _ => None,
+    }
+}
+
+/// Native satellite number of PRN that is known to belong to `gnss_type`
+pub fn from_constellation_prn(gnss_type: &GnssType, prn: u32) -> Option<u32> {
+    match (gnss_type, prn) {
+        (&GnssType::Gps, 1..=32) => Some(prn),
+        (&GnssType::Sbas, 33..=64) => Some(prn + 87),
+        (&GnssType::Sbas, 120..=158) => Some(prn),
+        (&GnssType::Glonass, 1..=32) => Some(prn),
+        (&GnssType::Glonass, 65..=96) => Some(prn - 64),
+        (&GnssType::Galileo, 1..=36) => Some(prn),
+        (&GnssType::Galileo, 301..=336) => Some(prn - 300),
+        (&GnssType::Beidou, 1..=63) => Some(prn),
+        (&GnssType::Beidou, 201..=263) => Some(prn - 200),
+        (&GnssType::Beidou, 401..=463) => Some(prn - 400),
+        (&GnssType::Qzss, 1..=10) => Some(prn + 192),
+        (&GnssType::Qzss, 193..=202) => Some(prn),
+        (&GnssType::Navic, 1..=14) => Some(prn),
+        _ => None,
+    }
+}
+
+/// Constellation and native satellite number of the satellite
+///
+/// `system_id` is the NMEA 4.10 GNSS system ID, if the sentence has one.
+/// It takes precedence over the talker id.
+pub fn identify(talker_id: &[u8], system_id: Option<u8>, prn: u32) -> Option<(GnssType, u32)> {
+    let hint = match system_id {
+        Some(id) => GnssType::from_system_id(id),
+        None => GnssType::from_talker_id(talker_id),
+    };
+    classify(hint, prn)
+}
+
+/// Constellation and native satellite number of the satellite, when
+/// constellation hint is already resolved from talker id or system ID
+///
+/// GPS hint and no hint at all fall back to NMEA numbering, because SBAS
+/// and QZSS satellites are reported as GP, and GN may contain any
+/// constellation.
+pub fn classify(hint: Option<GnssType>, prn: u32) -> Option<(GnssType, u32)> {
+    match hint {
+        Some(GnssType::Gps) | None => from_nmea_prn(prn),
+        Some(gnss_type) => {
+            from_constellation_prn(&gnss_type, prn).map(|native| (gnss_type, native))
+        }
+    }
+}
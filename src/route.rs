@@ -0,0 +1,132 @@
+//! Assembly of multi-sentence RTE routes, with waypoint positions from WPL.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use parse::{RteData, RteMode, WplData};
+use page::{PageError, PageSequence, PageStatus};
+
+/// Waypoint of assembled route
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub id: String,
+    /// Position from the latest WPL with this ID, `None` if none arrived yet
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// All waypoints of one route, gathered from every sentence of one
+/// RTE cycle, in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub route_id: Option<String>,
+    pub mode: RteMode,
+    pub waypoints: Vec<Waypoint>,
+}
+
+impl Route {
+    /// Positions of all waypoints are known
+    pub fn is_resolved(&self) -> bool {
+        self.waypoints
+            .iter()
+            .all(|v| v.latitude.is_some() && v.longitude.is_some())
+    }
+}
+
+/// Result of feeding one RTE sentence into `RouteAssembler`, route is
+/// updated with `Complete`
+pub type RouteStatus = PageStatus;
+
+pub type RouteError = PageError;
+
+struct RouteIds {
+    mode: RteMode,
+    waypoint_ids: Vec<String>,
+}
+
+/// Joins RTE sentences into routes and resolves their waypoint IDs
+/// against positions received in WPL
+///
+/// Each route ID has its own cycle, so sentences of different routes
+/// may be interleaved. Waypoints are resolved when the route is requested,
+/// so WPL may arrive before or after the RTE.
+#[derive(Default)]
+pub struct RouteAssembler {
+    cycles: PageSequence<Option<String>, RouteIds>,
+    routes: BTreeMap<Option<String>, RouteIds>,
+    waypoints: BTreeMap<String, (f64, f64)>,
+}
+
+impl RouteAssembler {
+    pub fn new() -> RouteAssembler {
+        RouteAssembler::default()
+    }
+
+    /// Remember waypoint position. WPL without ID or position is ignored.
+    pub fn add_waypoint(&mut self, wpl: WplData) {
+        if let (Some(id), Some(lat), Some(lon)) = (wpl.waypoint_id, wpl.latitude, wpl.longitude) {
+            self.waypoints.insert(id, (lat, lon));
+        }
+    }
+
+    /// Feed one parsed RTE sentence
+    pub fn add(&mut self, data: RteData) -> Result<RouteStatus, RouteError> {
+        let mode = data.mode;
+        let waypoint_ids = data.waypoint_ids;
+        let (status, route) = self.cycles
+            .add(data.route_id.clone(),
+                 data.sentence_num,
+                 data.number_of_sentences,
+                 || {
+                     RouteIds {
+                         mode,
+                         waypoint_ids: Vec::new(),
+                     }
+                 },
+                 |route| {
+                     route.waypoint_ids.extend(waypoint_ids);
+                     Ok(())
+                 })?;
+        if let Some(route) = route {
+            self.routes.insert(data.route_id, route);
+        }
+        Ok(status)
+    }
+
+    /// Latest complete route with the ID, with waypoint positions
+    pub fn route(&self, route_id: Option<&str>) -> Option<Route> {
+        self.routes
+            .iter()
+            .find(|&(id, _)| id.as_ref().map(|v| v.as_str()) == route_id)
+            .map(|(id, ids)| self.resolve(id, ids))
+    }
+
+    /// Latest complete versions of all routes, with waypoint positions
+    pub fn routes<'a>(&'a self) -> impl Iterator<Item = Route> + 'a {
+        self.routes.iter().map(move |(id, ids)| self.resolve(id, ids))
+    }
+
+    /// Position of the waypoint from the latest WPL with this ID
+    pub fn waypoint(&self, id: &str) -> Option<(f64, f64)> {
+        self.waypoints.get(id).cloned()
+    }
+
+    fn resolve(&self, route_id: &Option<String>, ids: &RouteIds) -> Route {
+        Route {
+            route_id: route_id.clone(),
+            mode: ids.mode,
+            waypoints: ids.waypoint_ids
+                .iter()
+                .map(|id| {
+                         let pos = self.waypoints.get(id);
+                         Waypoint {
+                             id: id.clone(),
+                             latitude: pos.map(|v| v.0),
+                             longitude: pos.map(|v| v.1),
+                         }
+                     })
+                .collect(),
+        }
+    }
+}
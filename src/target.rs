@@ -0,0 +1,212 @@
+//! Table of ARPA radar targets from TTM and TLL.
+//!
+//! Radars repeat TTM and TLL of every tracked target on each sweep,
+//! so the table keeps the latest state of each target number and drops
+//! targets that are reported lost or are no longer reported.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use libm;
+
+use time::NaiveTime;
+use parse::{Bearing, OsdData, TargetAcquisition, TargetStatus, TllData, TtmData};
+
+/// Latest known state of radar target
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target {
+    pub target_number: u8,
+    pub name: Option<String>,
+    pub status: Option<TargetStatus>,
+    pub reference_target: bool,
+    pub acquisition: Option<TargetAcquisition>,
+    /// Distance from own ship, nautical miles, from TTM
+    pub distance: Option<f32>,
+    /// Bearing from own ship, from TTM
+    pub bearing: Option<Bearing>,
+    /// Knots, from TTM
+    pub speed: Option<f32>,
+    pub course: Option<Bearing>,
+    /// Distance of closest point of approach, nautical miles, from TTM
+    pub cpa_distance: Option<f32>,
+    /// Time until closest point of approach, minutes, from TTM
+    pub cpa_time: Option<f32>,
+    /// Position from TLL, cleared by a later TTM with distance and bearing
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub fix_time: Option<NaiveTime>,
+    /// Number of `TargetTable::tick` calls since the last update
+    pub age: u32,
+}
+
+impl Target {
+    fn new(target_number: u8) -> Target {
+        Target {
+            target_number,
+            name: None,
+            status: None,
+            reference_target: false,
+            acquisition: None,
+            distance: None,
+            bearing: None,
+            speed: None,
+            course: None,
+            cpa_distance: None,
+            cpa_time: None,
+            latitude: None,
+            longitude: None,
+            fix_time: None,
+            age: 0,
+        }
+    }
+
+    /// True bearing from own ship, degrees. Relative bearing is turned
+    /// by own ship `heading`, degrees true.
+    pub fn true_bearing(&self, heading: Option<f32>) -> Option<f32> {
+        match (self.bearing, heading) {
+            (Some(Bearing::Relative(bearing)), Some(heading)) => Some((bearing + heading) % 360.),
+            (Some(Bearing::Relative(_)), None) | (None, _) => None,
+            (Some(bearing), _) => Some(bearing.degrees()),
+        }
+    }
+
+    /// Target position as latitude/longitude pair in degrees: from TLL
+    /// if it is the latest report, otherwise from distance and bearing
+    /// relative to own ship position `own` and `heading`, degrees true.
+    ///
+    /// Uses local flat earth approximation, which is fine for radar ranges.
+    pub fn position(&self, own: (f64, f64), heading: Option<f32>) -> Option<(f64, f64)> {
+        if let (Some(lat), Some(lon)) = (self.latitude, self.longitude) {
+            return Some((lat, lon));
+        }
+        let (distance, bearing) = match (self.distance, self.true_bearing(heading)) {
+            (Some(distance), Some(bearing)) => (distance as f64, (bearing as f64).to_radians()),
+            _ => return None,
+        };
+        let (lat, lon) = own;
+        // one nautical mile is one minute of latitude
+        let north = distance * libm::cos(bearing) / 60.;
+        let east = distance * libm::sin(bearing) / 60.;
+        Some((lat + north, lon + east / libm::cos(lat.to_radians())))
+    }
+}
+
+/// Keeps the latest state of radar targets, keyed by target number
+pub struct TargetTable {
+    targets: BTreeMap<u8, Target>,
+    own_ship: Option<OsdData>,
+    max_age: u32,
+}
+
+impl TargetTable {
+    /// Targets not updated for more than `max_age` ticks are dropped
+    pub fn new(max_age: u32) -> TargetTable {
+        TargetTable {
+            targets: BTreeMap::new(),
+            own_ship: None,
+            max_age,
+        }
+    }
+
+    /// Update target from TTM, returns its new state
+    pub fn update_ttm(&mut self, ttm: TtmData) -> &Target {
+        let target = self.targets
+            .entry(ttm.target_number)
+            .or_insert_with(|| Target::new(ttm.target_number));
+        if ttm.distance.is_some() && ttm.bearing.is_some() {
+            // TLL position is older now
+            target.latitude = None;
+            target.longitude = None;
+        }
+        target.distance = ttm.distance;
+        target.bearing = ttm.bearing;
+        target.speed = ttm.speed;
+        target.course = ttm.course;
+        target.cpa_distance = ttm.cpa_distance;
+        target.cpa_time = ttm.cpa_time;
+        if ttm.name.is_some() {
+            target.name = ttm.name;
+        }
+        target.status = ttm.status;
+        target.reference_target = ttm.reference_target;
+        if ttm.acquisition.is_some() {
+            target.acquisition = ttm.acquisition;
+        }
+        if ttm.fix_time.is_some() {
+            target.fix_time = ttm.fix_time;
+        }
+        target.age = 0;
+        target
+    }
+
+    /// Update target from TLL, returns its new state
+    pub fn update_tll(&mut self, tll: TllData) -> &Target {
+        let target = self.targets
+            .entry(tll.target_number)
+            .or_insert_with(|| Target::new(tll.target_number));
+        target.latitude = tll.latitude;
+        target.longitude = tll.longitude;
+        if tll.name.is_some() {
+            target.name = tll.name;
+        }
+        target.status = tll.status;
+        target.reference_target = tll.reference_target;
+        if tll.fix_time.is_some() {
+            target.fix_time = tll.fix_time;
+        }
+        target.age = 0;
+        target
+    }
+
+    /// Remember own ship data, its heading is used for relative bearings
+    pub fn update_own_ship(&mut self, osd: OsdData) {
+        self.own_ship = Some(osd);
+    }
+
+    /// Own ship data from the latest OSD
+    pub fn own_ship(&self) -> Option<&OsdData> {
+        self.own_ship.as_ref()
+    }
+
+    /// Age all targets by one tick, for example one radar sweep.
+    /// Drops targets reported lost and targets not updated for more
+    /// than `max_age` ticks. Returns numbers of dropped targets.
+    pub fn tick(&mut self) -> Vec<u8> {
+        let max_age = self.max_age;
+        let dropped: Vec<u8> = self.targets
+            .values_mut()
+            .filter_map(|target| {
+                            target.age += 1;
+                            if target.status == Some(TargetStatus::Lost) || target.age > max_age {
+                                Some(target.target_number)
+                            } else {
+                                None
+                            }
+                        })
+            .collect();
+        for number in &dropped {
+            self.targets.remove(number);
+        }
+        dropped
+    }
+
+    pub fn target(&self, target_number: u8) -> Option<&Target> {
+        self.targets.get(&target_number)
+    }
+
+    /// All targets, ordered by target number
+    pub fn targets<'a>(&'a self) -> impl Iterator<Item = &'a Target> + 'a {
+        self.targets.values()
+    }
+
+    /// Position of target, see `Target::position`. Heading is taken
+    /// from the latest valid OSD.
+    pub fn target_position(&self, target_number: u8, own: (f64, f64)) -> Option<(f64, f64)> {
+        let heading = match self.own_ship {
+            Some(ref osd) if osd.heading_valid => osd.heading,
+            _ => None,
+        };
+        self.target(target_number)
+            .and_then(|target| target.position(own, heading))
+    }
+}
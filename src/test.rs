@@ -1,3 +1,8 @@
+use alloc::string::ToString;
+
+use super::*;
+use parse::*;
+use time::{NaiveDate, NaiveTime, NaiveDateTime};
 
 #[test]
 fn test_fix_type() {
@@ -18,9 +23,9 @@ fn test_checksum() {
     use parse::checksum;
     let valid = "$GNGSA,A,1,,,,,,,,,,,,,99.99,99.99,99.99*2E";
     let invalid = "$GNZDA,165118.00,13,05,2016,00,00*71";
-    assert_eq!(checksum((&valid[1..valid.len() - 3]).as_bytes().iter()),
+    assert_eq!(checksum(valid.as_bytes()[1..valid.len() - 3].iter()),
                0x2E);
-    assert_ne!(checksum((&invalid[1..invalid.len() - 3]).as_bytes().iter()),
+    assert_ne!(checksum(invalid.as_bytes()[1..invalid.len() - 3].iter()),
                0x71);
 }
 
@@ -32,13 +37,12 @@ fn test_message_type() {
 
 #[test]
 fn test_gga_north_west() {
-    use chrono::Timelike;
     let mut nmea = Nmea::new();
     nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
         .unwrap();
-    assert_eq!(nmea.fix_timestamp().unwrap().second(), 50);
-    assert_eq!(nmea.fix_timestamp().unwrap().minute(), 27);
-    assert_eq!(nmea.fix_timestamp().unwrap().hour(), 9);
+    assert_eq!(nmea.fix_timestamp().unwrap().sec, 50.);
+    assert_eq!(nmea.fix_timestamp().unwrap().min, 27);
+    assert_eq!(nmea.fix_timestamp().unwrap().hour, 9);
     assert_eq!(nmea.latitude().unwrap(), 53. + 21.6802 / 60.);
     assert_eq!(nmea.longitude().unwrap(), -(6. + 30.3372 / 60.));
     assert_eq!(nmea.fix_type().unwrap(), FixType::Gps);
@@ -84,13 +88,12 @@ fn test_gga_invalid() {
 
 #[test]
 fn test_gga_gps() {
-    use chrono::Timelike;
     let mut nmea = Nmea::new();
     nmea.parse("$GPGGA,092750.000,5321.6802,S,00630.3372,E,1,8,1.03,61.7,M,55.2,M,,*79")
         .unwrap();
-    assert_eq!(nmea.fix_timestamp().unwrap().second(), 50);
-    assert_eq!(nmea.fix_timestamp().unwrap().minute(), 27);
-    assert_eq!(nmea.fix_timestamp().unwrap().hour(), 9);
+    assert_eq!(nmea.fix_timestamp().unwrap().sec, 50.);
+    assert_eq!(nmea.fix_timestamp().unwrap().min, 27);
+    assert_eq!(nmea.fix_timestamp().unwrap().hour, 9);
     assert_eq!(-(53. + 21.6802 / 60.), nmea.latitude.unwrap());
     assert_eq!(6. + 30.3372 / 60., nmea.longitude.unwrap());
     assert_eq!(nmea.fix_type(), Some(FixType::Gps));
@@ -234,63 +237,63 @@ fn test_parse_for_fix() {
     {
         let mut nmea = Nmea::create_for_navigation([SentenceType::RMC, SentenceType::GGA]
                                                        .iter()
-                                                       .map(|v| v.clone())
+                                                       .cloned()
                                                        .collect())
                 .unwrap();
         let log = [("$GPRMC,123308.2,A,5521.76474,N,03731.92553,E,000.48,071.9,090317,010.2,E,A*3B",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 200))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.2 })),
                    ("$GPGGA,123308.2,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*52",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 200))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.2 })),
                    ("$GPVTG,071.9,T,061.7,M,000.48,N,0000.88,K,A*10",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 200))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.2 })),
                    ("$GPRMC,123308.3,A,5521.76474,N,03731.92553,E,000.51,071.9,090317,010.2,E,A*32",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 300))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.3 })),
                    ("$GPGGA,123308.3,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*53",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 300))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.3 })),
                    ("$GPVTG,071.9,T,061.7,M,000.51,N,0000.94,K,A*15",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 300))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.3 })),
                    ("$GPRMC,123308.4,A,5521.76474,N,03731.92553,E,000.54,071.9,090317,010.2,E,A*30",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 400))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.4 })),
                    ("$GPGGA,123308.4,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*54",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 400))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.4 })),
                    ("$GPVTG,071.9,T,061.7,M,000.54,N,0001.00,K,A*1C",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 400))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.4 })),
                    ("$GPRMC,123308.5,A,5521.76474,N,03731.92553,E,000.57,071.9,090317,010.2,E,A*32",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 500))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.5 })),
                    ("$GPGGA,123308.5,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*55",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 500))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.5 })),
                    ("$GPVTG,071.9,T,061.7,M,000.57,N,0001.05,K,A*1A",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 500))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.5 })),
                    ("$GPRMC,123308.6,A,5521.76474,N,03731.92553,E,000.58,071.9,090317,010.2,E,A*3E",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 600))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.6 })),
                    ("$GPGGA,123308.6,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*56",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 600))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.6 })),
                    ("$GPVTG,071.9,T,061.7,M,000.58,N,0001.08,K,A*18",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 600))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.6 })),
                    ("$GPRMC,123308.7,A,5521.76474,N,03731.92553,E,000.59,071.9,090317,010.2,E,A*3E",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 700))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.7 })),
                    ("$GPGGA,123308.7,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*57",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 700))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.7 })),
                    ("$GPVTG,071.9,T,061.7,M,000.59,N,0001.09,K,A*18",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 700)))];
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.7 }))];
 
         for (i, item) in log.iter().enumerate() {
             let res = nmea.parse_for_fix(item.0.as_bytes()).unwrap();
@@ -302,18 +305,22 @@ fn test_parse_for_fix() {
     {
         let mut nmea = Nmea::create_for_navigation([SentenceType::RMC, SentenceType::GGA]
                                                        .iter()
-                                                       .map(|v| v.clone())
+                                                       .cloned()
                                                        .collect())
                 .unwrap();
         let log = [("$GPRMC,123308.2,A,5521.76474,N,03731.92553,E,000.48,071.9,090317,010.2,E,A*3B",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 200))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.2 })),
                    ("$GPRMC,123308.3,A,5521.76474,N,03731.92553,E,000.51,071.9,090317,010.2,E,A*32",
                     FixType::Invalid,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 300))),
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.3 })),
                    ("$GPGGA,123308.3,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*53",
                     FixType::Gps,
-                    Some(NaiveTime::from_hms_milli(12, 33, 8, 300)))];
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.3 })),
+                   // new epoch without RMC is not a fix
+                   ("$GPGGA,123308.4,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*54",
+                    FixType::Invalid,
+                    Some(NaiveTime { hour: 12, min: 33, sec: 8.4 }))];
 
         for (i, item) in log.iter().enumerate() {
             let res = nmea.parse_for_fix(item.0.as_bytes()).unwrap();
@@ -321,6 +328,7 @@ fn test_parse_for_fix() {
             assert_eq!((&res, &nmea.fix_time), (&item.1, &item.2));
         }
     }
+    assert!(Nmea::create_for_navigation(Default::default()).is_err());
 }
 
 #[test]
@@ -337,7 +345,7 @@ fn test_some_reciever() {
                  "$GPRMC,171727.000,A,6847.2474,N,03245.8353,E,0.49,42.80,250317,,*32"];
     let mut nmea = Nmea::create_for_navigation([SentenceType::RMC, SentenceType::GGA]
                                                    .iter()
-                                                   .map(|v| v.clone())
+                                                   .cloned()
                                                    .collect())
             .unwrap();
     println!("start test");
@@ -366,8 +374,8 @@ fn test_parse_rmc() {
     assert_eq!(s.checksum, s.calc_checksum());
     assert_eq!(s.checksum, 0x2b);
     let rmc_data = parse_rmc(&s).unwrap();
-    assert_eq!(rmc_data.fix_time.unwrap(), NaiveTime::from_hms_milli(22, 54, 46, 330));
-    assert_eq!(rmc_data.fix_date.unwrap(), NaiveDate::from_ymd(94, 11, 19));
+    assert_eq!(rmc_data.fix_time.unwrap(), NaiveTime { hour: 22, min: 54, sec: 46.33 });
+    assert_eq!(rmc_data.fix_date.unwrap(), NaiveDate { year: 94, month: 11, day: 19 });
 
     println!("lat: {}", rmc_data.lat.unwrap());
     relative_eq!(rmc_data.lat.unwrap(), 49.0 + 16.45 / 60.);
@@ -393,15 +401,15 @@ fn test_parse_rmc() {
 
 #[test]
 fn test_float_number() {
-    assert_eq!(IResult::Done(&b""[..], &b"12.3"[..]), float_number(&b"12.3"[..]));
-    assert_eq!(IResult::Done(&b"a"[..], &b"12.3"[..]), float_number(&b"12.3a"[..]));
-    assert_eq!(IResult::Done(&b"a"[..], &b"12"[..]), float_number(&b"12a"[..]));
-    assert_eq!(IResult::Error(nom::ErrorKind::Digit), float_number(&b"a12a"[..]));
+    assert_eq!(Ok((&b""[..], &b"12.3"[..])), float_number(&b"12.3"[..]));
+    assert_eq!(Ok((&b"a"[..], &b"12.3"[..])), float_number(&b"12.3a"[..]));
+    assert_eq!(Ok((&b"a"[..], &b"12"[..])), float_number(&b"12a"[..]));
+    assert!(float_number(&b"a12a"[..]).is_err());
 }
 
 #[test]
 fn test_parse_vtg() {
-    let run_parse_vtg = |line: &str| -> Result<VtgData, String> {
+    let run_parse_vtg = |line: &str| -> Result<VtgData> {
         let s = parse_nmea_sentence(line.as_bytes()).expect("VTG sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_vtg(&s)
@@ -423,27 +431,27 @@ fn test_parse_gsv_full() {
                              checksum: 0,
                          })
             .unwrap();
-    assert_eq!(data.gnss_type, GnssType::Gps);
+    assert_eq!(data.gnss_type, Some(GnssType::Gps));
     assert_eq!(data.number_of_sentences, 2);
     assert_eq!(data.sentence_num, 1);
     assert_eq!(data._sats_in_view, 8);
     assert_eq!(data.sats_info[0].clone().unwrap(),
                Satellite {
-                   gnss_type: data.gnss_type.clone(), prn: 1, elevation: None,
-                   azimuth: Some(83.), snr: Some(46.)
+                   gnss_type: GnssType::Gps, prn: 1, elevation: None,
+                   azimuth: Some(83.), snr: Some(46.), used: false
                });
     assert_eq!(data.sats_info[1].clone().unwrap(),
                Satellite {
-                   gnss_type: data.gnss_type.clone(), prn: 2, elevation: Some(17.),
-                   azimuth: Some(308.), snr: None});
+                   gnss_type: GnssType::Gps, prn: 2, elevation: Some(17.),
+                   azimuth: Some(308.), snr: None, used: false});
     assert_eq!(data.sats_info[2].clone().unwrap(),
                Satellite {
-                   gnss_type: data.gnss_type.clone(), prn: 12, elevation: Some(7.),
-                   azimuth: Some(344.), snr: Some(39.)});
+                   gnss_type: GnssType::Gps, prn: 12, elevation: Some(7.),
+                   azimuth: Some(344.), snr: Some(39.), used: false});
     assert_eq!(data.sats_info[3].clone().unwrap(),
                Satellite {
-                   gnss_type: data.gnss_type.clone(), prn: 14, elevation: Some(22.),
-                   azimuth: Some(228.), snr: None});
+                   gnss_type: GnssType::Gps, prn: 14, elevation: Some(22.),
+                   azimuth: Some(228.), snr: None, used: false});
 
     let data = parse_gsv(&NmeaSentence {
                              talker_id: b"GL",
@@ -452,7 +460,7 @@ fn test_parse_gsv_full() {
                              checksum: 0,
                          })
             .unwrap();
-    assert_eq!(data.gnss_type, GnssType::Glonass);
+    assert_eq!(data.gnss_type, Some(GnssType::Glonass));
     assert_eq!(data.number_of_sentences, 3);
     assert_eq!(data.sentence_num, 3);
     assert_eq!(data._sats_in_view, 10);
@@ -460,17 +468,10 @@ fn test_parse_gsv_full() {
 
 #[test]
 fn test_parse_hms() {
-    use chrono::Timelike;
     let (_, time) = parse_hms(b"125619,").unwrap();
-    assert_eq!(time.hour(), 12);
-    assert_eq!(time.minute(), 56);
-    assert_eq!(time.second(), 19);
-    assert_eq!(time.nanosecond(), 0);
+    assert_eq!(time, NaiveTime { hour: 12, min: 56, sec: 19. });
     let (_, time) = parse_hms(b"125619.5,").unwrap();
-    assert_eq!(time.hour(), 12);
-    assert_eq!(time.minute(), 56);
-    assert_eq!(time.second(), 19);
-    assert_eq!(time.nanosecond(), 5_00_000_000);
+    assert_eq!(time, NaiveTime { hour: 12, min: 56, sec: 19.5 });
 }
 
 
@@ -483,7 +484,7 @@ fn test_parse_gga_full() {
                              checksum: 0x57,
                          })
             .unwrap();
-    assert_eq!(data.fix_time.unwrap(), NaiveTime::from_hms(3, 37, 45));
+    assert_eq!(data.fix_time.unwrap(), NaiveTime { hour: 3, min: 37, sec: 45. });
     assert_eq!(data.fix_type.unwrap(), FixType::Gps);
     relative_eq!(data.latitude.unwrap(), 56. + 50.82344 / 60.);
     relative_eq!(data.longitude.unwrap(), 35. + 48.9778 / 60.);
@@ -520,12 +521,14 @@ fn test_parse_gga_with_optional_fields() {
 
 #[test]
 fn test_gsa_prn_fields_parse() {
-    let (_, ret) = gsa_prn_fields_parse(b"5,").unwrap();
+    // PRN fields are always followed by PDOP
+    let (rest, ret) = gsa_prn_fields_parse(b"5,1.7").unwrap();
     assert_eq!(vec![Some(5)], ret);
-    let (_, ret) = gsa_prn_fields_parse(b",").unwrap();
+    assert_eq!(rest, b"1.7");
+    let (_, ret) = gsa_prn_fields_parse(b",1.7").unwrap();
     assert_eq!(vec![None], ret);
 
-    let (_, ret) = gsa_prn_fields_parse(b",,5,6,").unwrap();
+    let (_, ret) = gsa_prn_fields_parse(b",,5,6,1.7").unwrap();
     assert_eq!(vec![None, None, Some(5), Some(6)], ret);
 }
 
@@ -537,9 +540,13 @@ fn smoke_test_parse_gsa() {
         mode1: GsaMode1::Automatic,
         mode2: GsaMode2::Fix3D,
         fix_sats_prn: vec![16,18,22,24],
+        fix_sats_slots: vec![None, None, None, None, None, Some(16), Some(18), None, Some(22),
+                             Some(24), None, None],
         pdop: Some(3.6),
         hdop: Some(2.1),
         vdop: Some(2.2),
+        gnss_type: Some(GnssType::Gps),
+        system_id: None,
     }, gsa);
     let gsa_examples = ["$GPGSA,A,3,19,28,14,18,27,22,31,39,,,,,1.7,1.0,1.3*35",
                         "$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E",
@@ -554,3 +561,1351 @@ fn smoke_test_parse_gsa() {
     }
 }
 
+#[test]
+fn test_nmea_merge_epoch() {
+    let mut nmea = Nmea::new();
+    let lines = ["$GPRMC,092750.000,A,5321.6802,N,00630.3372,W,0.02,31.66,280511,,,A*43",
+                 "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76",
+                 "$GPGSA,A,3,10,07,05,02,29,04,08,13,,,,,1.72,1.03,1.38*0A",
+                 "$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70",
+                 "$GPGSV,3,2,11,02,39,223,19,13,28,070,17,26,23,252,,04,14,186,14*79",
+                 "$GPGSV,3,3,11,29,09,301,24,16,09,020,,36,,,*76",
+                 "$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48"];
+    let types = [SentenceType::RMC, SentenceType::GGA, SentenceType::GSA, SentenceType::GSV,
+                 SentenceType::GSV, SentenceType::GSV, SentenceType::VTG];
+    for (line, ty) in lines.iter().zip(types.iter()) {
+        assert_eq!(nmea.parse(line).unwrap(), *ty);
+    }
+    assert_eq!(nmea.fix_timestamp(), Some(NaiveTime { hour: 9, min: 27, sec: 50. }));
+    assert_eq!(nmea.fix_date, Some(NaiveDate { year: 11, month: 5, day: 28 }));
+    assert_eq!(nmea.fix_type(), Some(FixType::Gps));
+    assert_eq!(nmea.latitude().unwrap(), 53. + 21.6802 / 60.);
+    assert_eq!(nmea.longitude().unwrap(), -(6. + 30.3372 / 60.));
+    assert_eq!(nmea.altitude(), Some(61.7));
+    assert_eq!(nmea.geoid_height(), Some(55.2));
+    assert_eq!(nmea.fix_satellites(), Some(8));
+    assert_eq!(nmea.pdop, Some(1.72));
+    assert_eq!(nmea.vdop, Some(1.38));
+    assert_eq!(nmea.fix_satellites_prns, Some(vec![10, 7, 5, 2, 29, 4, 8, 13]));
+    assert_eq!(nmea.speed_over_ground, Some(5.5));
+    assert_eq!(nmea.true_course, Some(54.7));
+    assert_eq!(nmea.satellites().len(), 11);
+    assert_eq!(nmea.satellites()[0].prn(), 10);
+
+    assert_eq!(SentenceType::try_from(b"GGA").unwrap(), SentenceType::GGA);
+    assert_eq!(SentenceType::try_from(b"XXX").unwrap(), SentenceType::None);
+}
+
+#[test]
+fn test_parse_gsv_last_sat() {
+    let s = parse_nmea_sentence(b"$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")
+        .unwrap();
+    let data = parse_gsv(&s).unwrap();
+    assert_eq!(data.sats_info[3].as_ref().map(|v| (v.prn, v.snr)), Some((8, Some(30.))));
+    let s = parse_nmea_sentence(b"$GPGSV,3,3,11,29,09,301,24,16,09,020,,36,,,*76").unwrap();
+    let data = parse_gsv(&s).unwrap();
+    assert_eq!(data.sats_info[2].as_ref().map(|v| (v.prn, v.elevation)), Some((36, None)));
+    assert!(data.sats_info[3].is_none());
+}
+
+#[test]
+fn test_gsv_assembler() {
+    let gsv = |line: &str| parse_gsv(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let mut asm = GsvAssembler::new();
+    let gsa = parse_gsa(&parse_nmea_sentence(b"$GPGSA,A,3,10,07,05,02,29,04,08,13,,,,,1.72,1.03,1.38*0A")
+                            .unwrap())
+            .unwrap();
+    asm.update_used(&gsa);
+    assert_eq!(asm.add(gsv("$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")),
+               Ok(GsvStatus::Pending));
+    assert_eq!(asm.add(gsv("$GLGSV,3,3,10,72,40,075,43,87,00,000,*6F")),
+               Err(GsvError::MissingPage { expected: 1, received: 3 }));
+    assert_eq!(asm.add(gsv("$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")),
+               Ok(GsvStatus::Restarted { dropped_pages: 1 }));
+    assert_eq!(asm.add(gsv("$GPGSV,3,2,11,02,39,223,19,13,28,070,17,26,23,252,,04,14,186,14*79")),
+               Ok(GsvStatus::Pending));
+    assert_eq!(asm.add(gsv("$GPGSV,3,2,11,02,39,223,19,13,28,070,17,26,23,252,,04,14,186,14*79")),
+               Err(GsvError::DuplicatePage(2)));
+    assert!(asm.sky_view(Some(GnssType::Gps), None).is_none());
+    assert_eq!(asm.add(gsv("$GPGSV,3,3,11,29,09,301,24,16,09,020,,36,,,*76")),
+               Ok(GsvStatus::Complete { dropped_pages: 0 }));
+
+    let view = asm.sky_view(Some(GnssType::Gps), None).unwrap();
+    assert_eq!(view.satellites.len(), 11);
+    let used: Vec<u32> = view.satellites.iter().filter(|s| s.used()).map(|s| s.prn()).collect();
+    assert_eq!(used, vec![10, 7, 5, 8, 2, 13, 4, 29]);
+
+    assert_eq!(asm.add(gsv("$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")),
+               Ok(GsvStatus::Pending));
+    assert_eq!(asm.add(gsv("$GPGSV,3,3,11,29,09,301,24,16,09,020,,36,,,*76")),
+               Err(GsvError::MissingPage { expected: 2, received: 3 }));
+    assert_eq!(asm.add(gsv("$GLGSV,1,1,03,72,40,075,43,87,00,000,*6F")),
+               Err(GsvError::SatsInViewMismatch { announced: 3, received: 2 }));
+    assert_eq!(asm.sky_views().count(), 2);
+}
+
+#[test]
+fn test_gsv_mixed_gn_cycle() {
+    let gsv = |line: &str| parse_gsv(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let gsa = |line: &str| parse_gsa(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let page1 = "$GNGSV,2,1,07,02,39,223,19,13,28,070,17,26,23,252,,04,14,186,14*62";
+    let page2 = "$GNGSV,2,2,07,65,40,075,43,72,00,000,,87,10,120,30*59";
+
+    let mut asm = GsvAssembler::new();
+    asm.update_used(&gsa("$GPGSA,A,3,02,13,04,,,,,,,,,,1.72,1.03,1.38*0A"));
+    asm.update_used(&gsa("$GLGSA,A,3,65,87,,,,,,,,,,,1.72,1.03,1.38*1E"));
+    assert_eq!(asm.add(gsv(page1)), Ok(GsvStatus::Pending));
+    assert_eq!(asm.add(gsv(page2)), Ok(GsvStatus::Complete { dropped_pages: 0 }));
+    let view = asm.sky_view(None, None).unwrap();
+    let sats: Vec<(GnssType, u32, bool)> = view.satellites
+        .iter()
+        .map(|s| (s.gnss_type(), s.prn(), s.used()))
+        .collect();
+    assert_eq!(sats,
+               vec![(GnssType::Gps, 2, true),
+                    (GnssType::Gps, 13, true),
+                    (GnssType::Gps, 26, false),
+                    (GnssType::Gps, 4, true),
+                    (GnssType::Glonass, 65, true),
+                    (GnssType::Glonass, 72, false),
+                    (GnssType::Glonass, 87, true)]);
+
+    let mut nmea = Nmea::new();
+    nmea.parse(page1).unwrap();
+    nmea.parse(page2).unwrap();
+    assert_eq!(nmea.satellites().len(), 7);
+    // next cycle has no GLONASS satellites, none of them must be left over
+    nmea.parse("$GNGSV,1,1,02,02,39,223,19,13,28,070,17*6F").unwrap();
+    let prns: Vec<u32> = nmea.satellites().iter().map(|s| s.prn()).collect();
+    assert_eq!(prns, vec![2, 13]);
+}
+
+#[test]
+fn test_gsv_used_from_several_gngsa() {
+    let gsv = |line: &str| parse_gsv(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let gsa = |line: &str| parse_gsa(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let used = |asm: &GsvAssembler, gnss_type: Option<GnssType>| -> Vec<(u32, bool)> {
+        asm.sky_view(gnss_type, None)
+            .unwrap()
+            .satellites
+            .iter()
+            .map(|s| (s.prn(), s.used()))
+            .collect()
+    };
+
+    let mut asm = GsvAssembler::new();
+    // 15 GPS satellites don't fit into one GSA
+    asm.update_used(&gsa("$GNGSA,A,3,02,05,07,08,10,12,13,15,16,18,20,21,1.2,0.7,1.0*2A"));
+    asm.update_used(&gsa("$GNGSA,A,3,24,26,29,,,,,,,,,,1.2,0.7,1.0*20"));
+    assert_eq!(asm.add(gsv("$GPGSV,1,1,04,24,45,120,40,26,30,200,35,29,10,300,20,31,05,010,*73")),
+               Ok(GsvStatus::Complete { dropped_pages: 0 }));
+    assert_eq!(asm.add(gsv("$GNGSV,1,1,04,05,45,120,40,24,30,200,35,31,10,300,20,66,05,010,*67")),
+               Ok(GsvStatus::Complete { dropped_pages: 0 }));
+    assert_eq!(used(&asm, Some(GnssType::Gps)),
+               vec![(24, true), (26, true), (29, true), (31, false)]);
+    assert_eq!(used(&asm, None),
+               vec![(5, true), (24, true), (31, false), (66, false)]);
+
+    // GSA after GSV pages belongs to the next epoch
+    asm.update_used(&gsa("$GNGSA,A,3,65,66,,,,,,,,,,,1.2,0.7,1.0*2A"));
+    assert_eq!(used(&asm, Some(GnssType::Gps)),
+               vec![(24, false), (26, false), (29, false), (31, false)]);
+    assert_eq!(used(&asm, None),
+               vec![(5, false), (24, false), (31, false), (66, true)]);
+}
+
+#[test]
+fn test_gsv_assembler_signals() {
+    let gsv = |line: &str| parse_gsv(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let mut asm = GsvAssembler::new();
+    assert_eq!(asm.add(gsv("$GPGSV,2,1,05,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30,1*69")),
+               Ok(GsvStatus::Pending));
+    assert_eq!(asm.add(gsv("$GPGSV,1,1,02,10,63,137,30,07,61,098,28,8*66")),
+               Ok(GsvStatus::Complete { dropped_pages: 0 }));
+    assert_eq!(asm.add(gsv("$GPGSV,2,2,05,02,39,223,19,1*52")),
+               Ok(GsvStatus::Complete { dropped_pages: 0 }));
+
+    let l1 = asm.sky_view(Some(GnssType::Gps), Some(1)).unwrap();
+    assert_eq!(l1.signal(), Some(Signal::GpsL1Ca));
+    assert_eq!(l1.satellites.len(), 5);
+    assert_eq!(l1.satellites[0].snr(), Some(17.));
+    let l5 = asm.sky_view(Some(GnssType::Gps), Some(8)).unwrap();
+    assert_eq!(l5.signal(), Some(Signal::GpsL5Q));
+    assert_eq!(l5.satellites.len(), 2);
+    assert_eq!(l5.satellites[0].snr(), Some(30.));
+    assert!(asm.sky_view(Some(GnssType::Gps), None).is_none());
+    assert_eq!(asm.sky_views().count(), 2);
+}
+
+#[test]
+fn test_parse_gsv_nmea_4_10() {
+    let gsv = match parse(b"$GAGSV,3,1,09,02,46,103,38,03,42,063,40,05,25,127,37,08,22,064,,7*7D")
+              .unwrap() {
+        ParseResult::GSV(gsv) => gsv,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(gsv.gnss_type, Some(GnssType::Galileo));
+    assert_eq!(gsv.signal_id, Some(7));
+    assert_eq!(gsv.signal(), Some(Signal::GalileoL1BC));
+    assert_eq!(gsv.sats_info[3].as_ref().map(|v| (v.prn, v.snr)), Some((8, None)));
+
+    let gsv = match parse(b"$GNGSV,3,1,10,01,40.5,083.2,46.7,66,17,308,,1*66").unwrap() {
+        ParseResult::GSV(gsv) => gsv,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(gsv.gnss_type, None);
+    assert_eq!(gsv.signal(), Some(Signal::GpsL1Ca));
+    let sat = gsv.sats_info[0].clone().unwrap();
+    assert_eq!((sat.elevation(), sat.azimuth(), sat.snr()), (Some(40.5), Some(83.2), Some(46.7)));
+    assert_eq!(gsv.sats_info[1].as_ref().map(|v| v.gnss_type()), Some(GnssType::Glonass));
+    assert!(gsv.sats_info[2].is_none());
+
+    for talker in [&b"BD"[..], b"GB", b"QZ", b"GI"].iter() {
+        parse_gsv(&NmeaSentence {
+                      talker_id: talker,
+                      message_id: b"GSV",
+                      data: b"1,1,01,10,63,137,17",
+                      checksum: 0,
+                  })
+                .unwrap();
+    }
+}
+
+#[test]
+fn test_prn_identify() {
+    use prn::identify;
+    assert_eq!(identify(b"GP", None, 12), Some((GnssType::Gps, 12)));
+    assert_eq!(identify(b"GP", None, 46), Some((GnssType::Sbas, 133)));
+    assert_eq!(identify(b"GP", None, 194), Some((GnssType::Qzss, 194)));
+    assert_eq!(identify(b"GP", None, 201), Some((GnssType::Qzss, 201)));
+    assert_eq!(identify(b"GN", None, 202), Some((GnssType::Qzss, 202)));
+    assert_eq!(identify(b"QZ", None, 202), Some((GnssType::Qzss, 202)));
+    assert_eq!(identify(b"QZ", None, 10), Some((GnssType::Qzss, 202)));
+    assert_eq!(identify(b"GL", None, 75), Some((GnssType::Glonass, 11)));
+    assert_eq!(identify(b"GN", None, 75), Some((GnssType::Glonass, 11)));
+    assert_eq!(identify(b"GN", None, 214), Some((GnssType::Beidou, 14)));
+    assert_eq!(identify(b"GN", None, 305), Some((GnssType::Galileo, 5)));
+    assert_eq!(identify(b"GN", None, 97), None);
+    assert_eq!(identify(b"GA", None, 5), Some((GnssType::Galileo, 5)));
+    assert_eq!(identify(b"GB", None, 214), Some((GnssType::Beidou, 14)));
+    assert_eq!(identify(b"QZ", None, 2), Some((GnssType::Qzss, 194)));
+    assert_eq!(identify(b"GI", None, 3), Some((GnssType::Navic, 3)));
+    assert_eq!(identify(b"GA", None, 75), None);
+    assert_eq!(identify(b"GN", Some(3), 5), Some((GnssType::Galileo, 5)));
+    assert_eq!(identify(b"GN", Some(4), 14), Some((GnssType::Beidou, 14)));
+    assert_eq!(identify(b"GN", Some(1), 40), Some((GnssType::Sbas, 127)));
+    assert_eq!(identify(b"GN", Some(9), 5), Some((GnssType::Gps, 5)));
+
+    let gsv = parse_gsv(&parse_nmea_sentence(b"$GPGSV,1,1,02,10,63,137,17,46,30,200,40*7B")
+                            .unwrap())
+            .unwrap();
+    assert_eq!(gsv.gnss_type, Some(GnssType::Gps));
+    assert_eq!(gsv.sats_info[0].as_ref().map(|v| v.gnss_type()), Some(GnssType::Gps));
+    assert_eq!(gsv.sats_info[1].as_ref().map(|v| v.gnss_type()), Some(GnssType::Sbas));
+}
+
+#[test]
+fn test_gsa_merger() {
+    let gsa = |line: &str| parse_gsa(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let mut merger = GsaMerger::new();
+
+    assert_eq!(merger.add(gsa("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E")), None);
+    assert_eq!(merger.add(gsa("$BDGSA,A,3,214,,,,,,,,,,,,1.8,1.1,1.4*18")), None);
+    let merged = merger.add(gsa("$GNGSA,A,3,31,26,21,,,,,,,,,,3.77,2.55,2.77*1A")).unwrap();
+    assert_eq!(merged.fix_sats.len(), 7);
+    assert_eq!(merged.fix_sats_of(&GnssType::Beidou).collect::<Vec<_>>(),
+               vec![&FixSatellite { gnss_type: GnssType::Beidou, prn: 214, svid: 14 }]);
+    assert_eq!((merged.pdop, merged.hdop, merged.vdop), (Some(1.8), Some(1.1), Some(1.4)));
+    assert!(!merged.dop_mismatch);
+
+    assert_eq!(merger.add(gsa("$GNGSA,A,3,75,86,87,,,,,,,,,,3.77,2.55,2.77*1C")), None);
+    let merged = merger.finish().unwrap();
+    assert_eq!(merged.fix_sats_of(&GnssType::Gps).count(), 3);
+    assert_eq!(merged.fix_sats_of(&GnssType::Glonass).map(|v| v.svid).collect::<Vec<_>>(),
+               vec![11, 22, 23]);
+    assert!(merger.finish().is_none());
+
+    let data = gsa("$GNGSA,A,3,80,71,,,,,,,,,,,1.83,1.09,1.47,2*0C");
+    assert_eq!((data.system_id, data.gnss_type.clone()), (Some(2), Some(GnssType::Glonass)));
+    assert_eq!(merger.add(gsa("$GNGSA,A,3,05,13,15,,,,,,,,,,1.83,1.09,1.47,1*02")), None);
+    assert_eq!(merger.add(data), None);
+    let merged = merger.add(gsa("$GNGSA,A,3,05,,,,,,,,,,,,1.85,1.09,1.47,1*02")).unwrap();
+    assert_eq!(merged.fix_sats.len(), 5);
+    assert!(!merged.dop_mismatch);
+    assert_eq!(merger.add(gsa("$GNGSA,A,3,80,71,,,,,,,,,,,1.83,1.09,1.47,2*0C")), None);
+    assert!(merger.finish().unwrap().dop_mismatch);
+
+    assert_eq!(gsa("$GPGSA,A,1,,,,*32").fix_sats_prn, vec![]);
+}
+
+#[test]
+fn test_parse_gll() {
+    let gll = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::GLL(gll) => gll,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = gll("$GPGLL,4916.45,N,12311.12,W,225444,A,A*5C");
+    assert_relative_eq!(data.latitude.unwrap(), 49. + 16.45 / 60.);
+    assert_relative_eq!(data.longitude.unwrap(), -(123. + 11.12 / 60.));
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 22, min: 54, sec: 44. }));
+    assert!(data.valid);
+    assert_eq!(data.faa_mode, Some(FaaMode::Autonomous));
+
+    let data = gll("$GPGLL,4916.45,N,12311.12,W,225444,A*31");
+    assert!(data.valid);
+    assert_eq!(data.faa_mode, None);
+
+    assert_eq!(gll("$GPGLL,,,,,,V,N*64"),
+               GllData {
+                   latitude: None,
+                   longitude: None,
+                   fix_time: None,
+                   valid: false,
+                   faa_mode: Some(FaaMode::NotValid),
+               });
+
+    let mut nmea = Nmea::create_for_navigation([SentenceType::GLL].iter().cloned().collect())
+        .unwrap();
+    assert_eq!(nmea.parse_for_fix(b"$GNGLL,5521.76474,N,03731.92553,E,123308.20,A,D*70").unwrap(),
+               FixType::DGps);
+    assert_eq!(nmea.parse_for_fix(b"$GPGLL,,,,,,V,N*64").unwrap(), FixType::Invalid);
+    assert_eq!(nmea.latitude(), None);
+}
+
+#[test]
+fn test_parse_zda() {
+    let zda = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::ZDA(zda) => zda,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = zda("$GPZDA,160012.71,11,03,2004,-1,00*7D");
+    assert_eq!(data.utc_date_time(),
+               Some(NaiveDateTime {
+                        date: NaiveDate { year: 2004, month: 3, day: 11 },
+                        time: NaiveTime { hour: 16, min: 0, sec: 12.71 },
+                    }));
+    assert_eq!(data.local_offset_minutes(), Some(-60));
+    assert_eq!(zda("$GNZDA,165118.00,13,05,2016,05,30*76").local_offset_minutes(),
+               Some(5 * 60 + 30));
+    // the sign of zero hours applies to minutes
+    let data = zda("$GPZDA,160012.71,11,03,2004,-00,30*4F");
+    assert_eq!((data.local_zone_hours, data.local_zone_minutes), (Some(0), Some(-30)));
+    assert_eq!(data.local_offset_minutes(), Some(-30));
+    assert_eq!(zda("$GPZDA,160012.71,11,03,2004,-03,30*4C").local_offset_minutes(),
+               Some(-(3 * 60 + 30)));
+    assert_eq!(zda("$GPZDA,,,,,,*48"),
+               ZdaData {
+                   utc_time: None,
+                   utc_date: None,
+                   local_zone_hours: None,
+                   local_zone_minutes: None,
+               });
+}
+
+#[test]
+fn test_parse_gst() {
+    let gst = match parse(b"$GPGST,133006.00,3.6,4.1,2.7,127.4,3.6,4.0,6.2*50").unwrap() {
+        ParseResult::GST(gst) => gst,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(gst.fix_time, Some(NaiveTime { hour: 13, min: 30, sec: 6. }));
+    assert_eq!((gst.rms, gst.semi_major, gst.semi_minor, gst.orientation),
+               (Some(3.6), Some(4.1), Some(2.7), Some(127.4)));
+    assert_eq!((gst.lat_error, gst.lon_error, gst.alt_error), (Some(3.6), Some(4.0), Some(6.2)));
+
+    assert_relative_eq!(gst.horizontal_error_95().unwrap(),
+                        2.4477 * ::libm::sqrtf((3.6 * 3.6 + 4.0 * 4.0) / 2.),
+                        epsilon = 1e-4);
+    let ellipse = gst.error_ellipse_95().unwrap();
+    assert_relative_eq!(ellipse.semi_major, 4.1 * 2.4477, epsilon = 1e-4);
+    assert_relative_eq!(ellipse.semi_minor, 2.7 * 2.4477, epsilon = 1e-4);
+
+    let ellipse = ErrorEllipse { semi_major: 10., semi_minor: 5., orientation: 90. };
+    let (north, east) = ellipse.offset(0.);
+    assert_relative_eq!(north, 0., epsilon = 1e-9);
+    assert_relative_eq!(east, 10., epsilon = 1e-9);
+
+    let gga = GgaData {
+        fix_time: None,
+        fix_type: Some(FixType::Gps),
+        latitude: Some(60.),
+        longitude: Some(30.),
+        fix_satellites: None,
+        hdop: None,
+        altitude: None,
+        geoid_height: None,
+    };
+    let outline = ellipse.outline(&gga, 4).unwrap();
+    assert_eq!(outline.len(), 4);
+    assert_relative_eq!(outline[0].0, 60., epsilon = 1e-9);
+    // 10 meters east at 60 degrees of latitude
+    assert_relative_eq!(outline[0].1, 30. + 20. / 6_378_137. * 180. / ::core::f64::consts::PI,
+                        epsilon = 1e-9);
+    assert_relative_eq!(outline[1].0, 60. - 5. / 6_378_137. * 180. / ::core::f64::consts::PI,
+                        epsilon = 1e-9);
+
+    let gst = parse_gst(&parse_nmea_sentence(b"$GPGST,,,,,,,,*57").unwrap()).unwrap();
+    assert_eq!(gst.horizontal_error_95(), None);
+    assert_eq!(gst.error_ellipse(), None);
+}
+
+#[test]
+fn test_parse_gns() {
+    let gns = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::GNS(gns) => gns,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = gns("$GNGNS,122310.2,3722.425671,N,12258.856215,W,AAN,14,0.9,1005.543,6.5,,,V*40");
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 12, min: 23, sec: 10.2 }));
+    assert_relative_eq!(data.latitude.unwrap(), 37. + 22.425671 / 60., epsilon = 1e-9);
+    assert_relative_eq!(data.longitude.unwrap(), -(122. + 58.856215 / 60.), epsilon = 1e-9);
+    assert_eq!(data.modes,
+               vec![FaaMode::Autonomous, FaaMode::Autonomous, FaaMode::NotValid]);
+    assert_eq!(data.mode(&GnssType::Glonass), Some(FaaMode::Autonomous));
+    assert_eq!(data.mode(&GnssType::Galileo), Some(FaaMode::NotValid));
+    assert_eq!(data.mode(&GnssType::Beidou), None);
+    assert_eq!(data.contributing(),
+               vec![(GnssType::Gps, FixType::Gps), (GnssType::Glonass, FixType::Gps)]);
+    assert_eq!(data.fix_type(), FixType::Gps);
+    assert_eq!((data.fix_satellites, data.hdop, data.altitude, data.geoid_height),
+               (Some(14), Some(0.9), Some(1005.543), Some(6.5)));
+    assert_eq!((data.dgps_age, data.dgps_station_id), (None, None));
+    assert_eq!(data.nav_status, Some(NavigationalStatus::NotValid));
+
+    let data = gns("$GNGNS,122310.2,3722.425671,N,12258.856215,W,NR,14,0.9,-5.5,6.5,1.2,0023*5F");
+    assert_eq!(data.fix_type(), FixType::Rtk);
+    assert_eq!(data.contributing(), vec![(GnssType::Glonass, FixType::Rtk)]);
+    assert_eq!(data.altitude, Some(-5.5));
+    assert_eq!((data.dgps_age, data.dgps_station_id), (Some(1.2), Some(23)));
+    assert_eq!(data.nav_status, None);
+
+    let data = gns("$GNGNS,,,,,,NNN,,,,,,,V*67");
+    assert_eq!(data.latitude, None);
+    assert_eq!(data.fix_type(), FixType::Invalid);
+
+    let mut nmea = Nmea::create_for_navigation([SentenceType::GNS].iter().cloned().collect())
+        .unwrap();
+    assert_eq!(nmea.parse_for_fix(b"$GNGNS,122310.2,3722.425671,N,12258.856215,W,NR,14,0.9,-5.5,6.5,1.2,0023*5F")
+                   .unwrap(),
+               FixType::Rtk);
+    assert_eq!(nmea.altitude(), Some(-5.5));
+    assert_eq!(nmea.parse_for_fix(b"$GNGNS,,,,,,NNN,,,,,,,V*67").unwrap(), FixType::Invalid);
+    assert_eq!(nmea.latitude(), None);
+}
+
+#[test]
+fn test_parse_gbs() {
+    let gbs = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::GBS(gbs) => gbs,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = gbs("$GPGBS,015509.00,-0.031,-0.186,0.219,19,0.000,-0.354,6.972*4D");
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 1, min: 55, sec: 9. }));
+    assert_eq!((data.lat_error, data.lon_error, data.alt_error),
+               (Some(-0.031), Some(-0.186), Some(0.219)));
+    assert_eq!(data.failed_prn, Some(19));
+    assert_eq!((data.missed_detection_probability, data.bias, data.bias_std_dev),
+               (Some(0.), Some(-0.354), Some(6.972)));
+    assert_eq!((data.system_id, data.signal_id), (None, None));
+    assert_eq!(data.failed_satellite(), Some((GnssType::Gps, 19)));
+
+    let gsv = |line: &str| parse_gsv(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let mut asm = GsvAssembler::new();
+    asm.add(gsv("$GPGSV,1,1,01,19,40,083,46*4D")).unwrap();
+    asm.add(gsv("$GAGSV,1,1,01,03,40,083,46*57")).unwrap();
+    let sat = asm.failed_satellite(&data).unwrap();
+    assert_eq!((sat.gnss_type(), sat.prn()), (GnssType::Gps, 19));
+
+    let data = gbs("$GNGBS,235503.00,1.6,1.4,3.2,3,0.012,-21.4,3.8,3,1*53");
+    assert_eq!((data.system_id, data.signal_id), (Some(3), Some(1)));
+    assert_eq!(data.failed_satellite(), Some((GnssType::Galileo, 3)));
+    let sat = asm.failed_satellite(&data).unwrap();
+    assert_eq!((sat.gnss_type(), sat.prn()), (GnssType::Galileo, 3));
+
+    let data = gbs("$GNGBS,170556.00,3.0,2.9,8.3,,,,,,*5C");
+    assert_eq!(data.failed_prn, None);
+    assert_eq!(data.bias, None);
+    assert!(asm.failed_satellite(&data).is_none());
+}
+
+#[test]
+fn test_parse_heading() {
+    match parse(b"$HEHDT,4.0,T*2B").unwrap() {
+        ParseResult::HDT(hdt) => assert_eq!(hdt.heading, Some(4.)),
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$HCHDM,238.5,M*25").unwrap() {
+        ParseResult::HDM(hdm) => assert_eq!(hdm.heading, Some(238.5)),
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$INTHS,77.52,E*24").unwrap() {
+        ParseResult::THS(ths) => {
+            assert_eq!(ths, ThsData { heading: Some(77.52), mode: Some(FaaMode::Estimated) })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    assert_eq!(parse_ths(&parse_nmea_sentence(b"$INTHS,,V*1E").unwrap()).unwrap(),
+               ThsData { heading: None, mode: Some(FaaMode::NotValid) });
+
+    let hdg = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::HDG(hdg) => hdg,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = hdg("$HCHDG,101.1,,,7.1,W*3C");
+    assert_eq!(data,
+               HdgData { heading: Some(101.1), deviation: None, variation: Some(-7.1) });
+    assert_relative_eq!(data.magnetic_heading().unwrap(), 101.1);
+    assert_relative_eq!(data.true_heading().unwrap(), 94., epsilon = 1e-4);
+
+    let data = hdg("$HCHDG,98.3,0.5,E,12.6,W*52");
+    assert_eq!(data.deviation, Some(0.5));
+    assert_relative_eq!(data.magnetic_heading().unwrap(), 98.8, epsilon = 1e-4);
+    assert_relative_eq!(data.true_heading().unwrap(), 86.2, epsilon = 1e-4);
+
+    assert_relative_eq!(hdg("$HCHDG,355.0,2.0,E,10.0,E*72").true_heading().unwrap(),
+                        7.,
+                        epsilon = 1e-4);
+
+    let data = hdg("$HCHDG,,,,,*6C");
+    assert_eq!(data, HdgData { heading: None, deviation: None, variation: None });
+    assert_eq!(data.true_heading(), None);
+}
+
+#[test]
+fn test_parse_wind() {
+    let mwv = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::MWV(mwv) => mwv,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = mwv("$WIMWV,214.8,R,0.1,K,A*28");
+    assert_eq!((data.wind_angle, data.reference, data.valid),
+               (Some(214.8), Some(WindReference::Relative), true));
+    assert_relative_eq!(data.wind_speed.unwrap(), 0.1 / 1.852, epsilon = 1e-6);
+    let data = mwv("$WIMWV,27,T,12.4,M,A*3A");
+    assert_eq!(data.reference, Some(WindReference::Theoretical));
+    assert_relative_eq!(data.wind_speed.unwrap(), 24.103667, epsilon = 1e-4);
+    assert_eq!(mwv("$WIMWV,,,,,V*28"),
+               MwvData { wind_angle: None, reference: None, wind_speed: None, valid: false });
+
+    let mwd = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::MWD(mwd) => mwd,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(mwd("$WIMWD,10.1,T,10.1,M,12,N,40,M*5D"),
+               MwdData {
+                   true_direction: Some(10.1),
+                   magnetic_direction: Some(10.1),
+                   wind_speed: Some(12.),
+               });
+    let data = mwd("$WIMWD,270,T,,,,,5.0,M*47");
+    assert_eq!(data.magnetic_direction, None);
+    assert_relative_eq!(data.wind_speed.unwrap(), 9.719222, epsilon = 1e-4);
+
+    let vwr = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::VWR(vwr) => vwr,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = vwr("$IIVWR,148.0,L,02.4,N,01.2,M,04.4,K*6F");
+    assert_eq!(data, VwrData { wind_angle: Some(-148.), wind_speed: Some(2.4) });
+    assert_eq!(data.wind_angle_clockwise(), Some(212.));
+    let data = vwr("$IIVWR,30.5,R,,,,,18.52,K*72");
+    assert_eq!(data.wind_angle_clockwise(), Some(30.5));
+    assert_relative_eq!(data.wind_speed.unwrap(), 10., epsilon = 1e-4);
+}
+
+#[test]
+fn test_parse_depth() {
+    match parse(b"$SDDBT,7.8,f,2.4,M,1.3,F*0D").unwrap() {
+        ParseResult::DBT(dbt) => assert_eq!(dbt.depth, Some(2.4)),
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$SDDBS,25.4,f,7.7,M,4.2,F*34").unwrap() {
+        ParseResult::DBS(dbs) => assert_eq!(dbs.depth, Some(7.7)),
+        res => panic!("unexpected result {:?}", res),
+    }
+    let dbt = parse_dbt(&parse_nmea_sentence(b"$SDDBT,10.0,f,,M,,F*37").unwrap()).unwrap();
+    assert_relative_eq!(dbt.depth.unwrap(), 3.048, epsilon = 1e-5);
+    let dbt = parse_dbt(&parse_nmea_sentence(b"$SDDBT,,f,,M,,F*28").unwrap()).unwrap();
+    assert_eq!(dbt.depth, None);
+
+    let dpt = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::DPT(dpt) => dpt,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = dpt("$INDPT,2.3,0.0*46");
+    assert_eq!(data, DptData { depth: Some(2.3), offset: Some(0.), max_range: None });
+    assert_eq!(data.depth_below_surface(), Some(2.3));
+    assert_eq!(data.depth_below_keel(), Some(2.3));
+
+    let data = dpt("$SDDPT,15.2,-1.5,100*55");
+    assert_eq!((data.offset, data.max_range), (Some(-1.5), Some(100.)));
+    assert_relative_eq!(data.depth_below_keel().unwrap(), 13.7, epsilon = 1e-5);
+    assert_eq!(data.depth_below_surface(), None);
+
+    let data = dpt("$SDDPT,15.2,0.4,*49");
+    assert_eq!(data.max_range, None);
+    assert_relative_eq!(data.depth_below_surface().unwrap(), 15.6, epsilon = 1e-5);
+    assert_eq!(data.depth_below_keel(), None);
+}
+
+#[test]
+fn test_parse_water_speed() {
+    match parse(b"$VWVHW,45.0,T,43.0,M,3.5,N,6.4,K*56").unwrap() {
+        ParseResult::VHW(vhw) => {
+            assert_eq!(vhw,
+                       VhwData {
+                           true_heading: Some(45.),
+                           magnetic_heading: Some(43.),
+                           speed_through_water: Some(3.5),
+                       })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    let vhw = parse_vhw(&parse_nmea_sentence(b"$VWVHW,,T,,M,,N,9.26,K*47").unwrap()).unwrap();
+    assert_eq!(vhw.true_heading, None);
+    assert_relative_eq!(vhw.speed_through_water.unwrap(), 5., epsilon = 1e-5);
+
+    let vbw = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::VBW(vbw) => vbw,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(vbw("$VMVBW,12.3,0.07,A,11.78,0.12,A,0.02,A,0.1,V*47"),
+               VbwData {
+                   water_speed_longitudinal: Some(12.3),
+                   water_speed_transverse: Some(0.07),
+                   water_speed_valid: true,
+                   ground_speed_longitudinal: Some(11.78),
+                   ground_speed_transverse: Some(0.12),
+                   ground_speed_valid: true,
+                   stern_water_speed_transverse: Some(0.02),
+                   stern_water_speed_valid: true,
+                   stern_ground_speed_transverse: Some(0.1),
+                   stern_ground_speed_valid: false,
+               });
+    let data = vbw("$VMVBW,-0.5,-0.1,A,,,V*4B");
+    assert_eq!((data.water_speed_longitudinal, data.water_speed_transverse),
+               (Some(-0.5), Some(-0.1)));
+    assert!(data.water_speed_valid);
+    assert_eq!(data.ground_speed_longitudinal, None);
+    assert!(!data.ground_speed_valid);
+    assert_eq!(data.stern_water_speed_transverse, None);
+
+    let vlw = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::VLW(vlw) => vlw,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(vlw("$IIVLW,7803.2,N,0.00,N*43"),
+               VlwData {
+                   total_water_distance: Some(7803.2),
+                   trip_water_distance: Some(0.),
+                   total_ground_distance: None,
+                   trip_ground_distance: None,
+               });
+    assert_eq!(vlw("$IIVLW,115.2,N,1.2,N,122.5,N,1.3,N*4F"),
+               VlwData {
+                   total_water_distance: Some(115.2),
+                   trip_water_distance: Some(1.2),
+                   total_ground_distance: Some(122.5),
+                   trip_ground_distance: Some(1.3),
+               });
+}
+
+#[test]
+fn test_parse_navigation() {
+    let xte = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::XTE(xte) => xte,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(xte("$GPXTE,A,A,0.67,L,N*6F"),
+               XteData {
+                   valid: true,
+                   cross_track_error: Some(0.67),
+                   steer: Some(SteerDirection::Left),
+                   faa_mode: None,
+               });
+    assert_eq!(xte("$GPXTE,V,V,,,N,N*5E"),
+               XteData {
+                   valid: false,
+                   cross_track_error: None,
+                   steer: None,
+                   faa_mode: Some(FaaMode::NotValid),
+               });
+
+    let apb = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::APB(apb) => apb,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = apb("$GPAPB,A,A,0.10,R,N,V,V,011,M,DEST,011,M,011,M*3C");
+    assert!(data.valid);
+    assert_eq!((data.cross_track_error, data.steer), (Some(0.1), Some(SteerDirection::Right)));
+    assert!(!data.arrival_circle_entered && !data.perpendicular_passed);
+    assert_eq!(data.bearing_origin_to_destination, Some(Bearing::Magnetic(11.)));
+    assert_eq!(data.destination_id, Some("DEST".to_string()));
+    assert_eq!(data.heading_to_steer.map(|v| v.degrees()), Some(11.));
+    assert_eq!(data.faa_mode, None);
+    let data = apb("$GPAPB,A,A,0.10,R,N,A,A,011,T,DEST,012,T,013,M,D*55");
+    assert!(data.arrival_circle_entered && data.perpendicular_passed);
+    assert_eq!(data.bearing_to_destination, Some(Bearing::True(12.)));
+    assert_eq!(data.heading_to_steer, Some(Bearing::Magnetic(13.)));
+    assert_eq!(data.faa_mode, Some(FaaMode::Differential));
+
+    let rmb = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::RMB(rmb) => rmb,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = rmb("$GPRMB,A,0.66,L,003,004,4917.24,N,12309.57,W,001.3,052.5,000.5,V*20");
+    assert!(data.valid && !data.arrived);
+    assert_eq!((data.cross_track_error, data.steer), (Some(0.66), Some(SteerDirection::Left)));
+    assert_eq!((data.origin_id.as_deref(), data.destination_id.as_deref()),
+               (Some("003"), Some("004")));
+    assert_relative_eq!(data.destination_latitude.unwrap(), 49. + 17.24 / 60.);
+    assert_relative_eq!(data.destination_longitude.unwrap(), -(123. + 9.57 / 60.));
+    assert_eq!((data.range_to_destination, data.bearing_to_destination, data.closing_velocity),
+               (Some(1.3), Some(52.5), Some(0.5)));
+    let data = rmb("$GPRMB,V,,,,,,,,,,,,V,N*04");
+    assert!(!data.valid);
+    assert_eq!((data.origin_id, data.destination_latitude), (None, None));
+    assert_eq!(data.faa_mode, Some(FaaMode::NotValid));
+
+    let bwc = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::BWC(bwc) => bwc,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = bwc("$GPBWC,220516,5130.02,N,00046.34,W,213.8,T,218.0,M,0004.6,N,EGLM*21");
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 22, min: 5, sec: 16. }));
+    assert_relative_eq!(data.waypoint_latitude.unwrap(), 51. + 30.02 / 60.);
+    assert_relative_eq!(data.waypoint_longitude.unwrap(), -46.34 / 60.);
+    assert_eq!((data.true_bearing, data.magnetic_bearing, data.distance),
+               (Some(213.8), Some(218.), Some(4.6)));
+    assert_eq!(data.waypoint_id, Some("EGLM".to_string()));
+    let data = bwc("$GPBWC,081837,,,,,,T,,M,,N,*13");
+    assert_eq!((data.waypoint_latitude, data.true_bearing, data.waypoint_id),
+               (None, None, None));
+
+    let bod = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::BOD(bod) => bod,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(bod("$GPBOD,099.3,T,105.6,M,POINTB,*48"),
+               BodData {
+                   true_bearing: Some(99.3),
+                   magnetic_bearing: Some(105.6),
+                   destination_id: Some("POINTB".to_string()),
+                   origin_id: None,
+               });
+    assert_eq!(bod("$GPBOD,097.0,T,103.2,M,POINTB,POINTA*4A").origin_id,
+               Some("POINTA".to_string()));
+}
+
+#[test]
+fn test_route_assembler() {
+    match parse(b"$GPWPL,4917.16,N,12310.64,W,003*65").unwrap() {
+        ParseResult::WPL(wpl) => {
+            assert_relative_eq!(wpl.latitude.unwrap(), 49. + 17.16 / 60.);
+            assert_relative_eq!(wpl.longitude.unwrap(), -(123. + 10.64 / 60.));
+            assert_eq!(wpl.waypoint_id, Some("003".to_string()));
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    let rte = match parse(b"$GPRTE,2,1,c,0,PBRCPK,PBRTO,PTELGR,PPLAND,PYAMBU,PPFAIR,PWARRN,PMORTL,PLISMR*73")
+              .unwrap() {
+        ParseResult::RTE(rte) => rte,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((rte.number_of_sentences, rte.sentence_num, rte.mode),
+               (2, 1, RteMode::Complete));
+    assert_eq!(rte.route_id, Some("0".to_string()));
+    assert_eq!(rte.waypoint_ids.len(), 9);
+    assert_eq!(rte.waypoint_ids[8], "PLISMR");
+
+    let rte = |line: &str| parse_rte(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let wpl = |line: &str| parse_wpl(&parse_nmea_sentence(line.as_bytes()).unwrap()).unwrap();
+    let mut asm = RouteAssembler::new();
+    asm.add_waypoint(wpl("$GPWPL,5100.00,N,00100.00,W,WP1*6A"));
+    assert_eq!(asm.add(rte("$GPRTE,2,1,w,R1,WP1,WP2*40")), Ok(RouteStatus::Pending));
+    assert_eq!(asm.add(rte("$GPRTE,2,1,w,R1,WP1,WP2*40")),
+               Ok(RouteStatus::Restarted { dropped_pages: 1 }));
+    assert_eq!(asm.route(Some("R1")), None);
+    assert_eq!(asm.add(rte("$GPRTE,2,2,w,R1,WP3*58")),
+               Ok(RouteStatus::Complete { dropped_pages: 0 }));
+    assert_eq!(asm.add(rte("$GPRTE,2,2,w,R1,WP3*58")),
+               Err(RouteError::MissingPage { expected: 1, received: 2 }));
+
+    let route = asm.route(Some("R1")).unwrap();
+    assert_eq!(route.mode, RteMode::Working);
+    assert_eq!(route.waypoints.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+               vec!["WP1", "WP2", "WP3"]);
+    assert_eq!((route.waypoints[0].latitude, route.waypoints[0].longitude),
+               (Some(51.), Some(-1.)));
+    assert_eq!(route.waypoints[1].latitude, None);
+    assert!(!route.is_resolved());
+
+    asm.add_waypoint(wpl("$GPWPL,5110.00,N,00110.00,W,WP2*69"));
+    asm.add_waypoint(wpl("$GPWPL,5120.00,N,00120.00,W,WP3*68"));
+    let route = asm.route(Some("R1")).unwrap();
+    assert!(route.is_resolved());
+    assert_relative_eq!(route.waypoints[2].longitude.unwrap(), -(1. + 20. / 60.));
+
+    assert_eq!(asm.add(rte("$GPRTE,2,1,c,R2,WP1,WP2*57")), Ok(RouteStatus::Pending));
+    // single page route replaces the unfinished one
+    assert_eq!(asm.add(rte("$GPRTE,1,1,c,R2,WP3,,WP1*79")),
+               Ok(RouteStatus::Complete { dropped_pages: 1 }));
+    assert_eq!(asm.routes().count(), 2);
+    let route = asm.route(Some("R2")).unwrap();
+    assert_eq!(route.waypoints.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+               vec!["WP3", "WP1"]);
+    assert_eq!(asm.waypoint("WP1"), Some((51., -1.)));
+}
+
+#[test]
+fn test_ais_assembler() {
+    let vdm = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::VDM(vdm) | ParseResult::VDO(vdm) => vdm,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = vdm("!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C");
+    assert_eq!((data.fragment_count, data.fragment_num, data.message_id, data.channel),
+               (1, 1, None, Some('B')));
+    assert!(!data.own_vessel);
+
+    let mut asm = AisAssembler::new();
+    let msg = asm.add(data).unwrap().unwrap();
+    assert_eq!(msg.message_type, 1);
+    assert_eq!(msg.bits.len(), 168);
+    assert_eq!(msg.bits.unsigned(8, 30), Some(366053209));
+
+    let first = vdm("!AIVDM,2,1,3,B,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0*3E");
+    assert_eq!(first.message_id, Some(3));
+    assert_eq!(asm.add(first), Ok(None));
+    // the same message on the other channel doesn't interfere
+    assert_eq!(asm.add(vdm("!AIVDM,2,1,4,A,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0*3A")),
+               Ok(None));
+    let msg = asm.add(vdm("!AIVDM,2,2,3,B,1@0000000000000,2*55")).unwrap().unwrap();
+    assert_eq!((msg.message_type, msg.channel), (5, Some('B')));
+    assert_eq!(msg.bits.len(), 424);
+    assert_eq!(msg.bits.unsigned(8, 30), Some(369190000));
+    assert_eq!(asm.add(vdm("!AIVDM,2,2,3,B,1@0000000000000,2*55")),
+               Err(AisError::MissingFragment { expected: 1, received: 2 }));
+
+    let data = vdm("!AIVDO,1,1,,,B39i>1000nTu;gQAlBj:wwS5kP06,0*5D");
+    assert!(data.own_vessel);
+    let msg = asm.add(data).unwrap().unwrap();
+    assert_eq!((msg.message_type, msg.own_vessel, msg.channel), (18, true, None));
+
+    assert_eq!(asm.add(vdm("!AIVDM,1,1,,A,x,0*5E")), Err(AisError::InvalidPayload(b'x')));
+    // only AIS sentences are encapsulated
+    match parse(b"!GPGGA,123308.2,5521.76474,N,03731.92553,E,1,08,2.2,211.5,M,13.1,M,,*52") {
+        Err(ParseError::InvalidMessageId) => {}
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let bits = ais::dearmor(b"w0", 2).unwrap();
+    assert_eq!(bits.len(), 10);
+    assert_eq!(bits.unsigned(0, 6), Some(63));
+    assert_eq!(bits.unsigned(6, 4), Some(0));
+    assert_eq!(bits.bit(10), None);
+}
+
+#[test]
+fn test_ais_decode() {
+    let decode = |line: &str| {
+        let vdm = match parse(line.as_bytes()).unwrap() {
+            ParseResult::VDM(vdm) | ParseResult::VDO(vdm) => vdm,
+            res => panic!("unexpected result {:?}", res),
+        };
+        AisAssembler::new().add(vdm).unwrap().unwrap().decode().unwrap()
+    };
+
+    let pos = match decode("!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C") {
+        AisReport::Position(pos) => pos,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((pos.message_type, pos.mmsi), (1, 366053209));
+    assert_eq!(pos.nav_status, Some(ais::NavigationStatus::RestrictedManoeuvrability));
+    assert_eq!(pos.rate_of_turn, Some(ais::RateOfTurn::Rate(0.)));
+    assert_eq!(pos.speed_over_ground, Some(0.));
+    assert!(!pos.high_accuracy);
+    assert_relative_eq!(pos.latitude.unwrap(), 37.802118, epsilon = 1e-6);
+    assert_relative_eq!(pos.longitude.unwrap(), -122.341618, epsilon = 1e-6);
+    assert_eq!((pos.course_over_ground, pos.true_heading, pos.timestamp),
+               (Some(219.3), Some(1), Some(59)));
+
+    let base = match decode("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D") {
+        AisReport::BaseStation(base) => base,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(base.mmsi, 3669702);
+    assert_eq!(base.utc,
+               Some(NaiveDateTime {
+                        date: NaiveDate { year: 2007, month: 5, day: 14 },
+                        time: NaiveTime { hour: 19, min: 57, sec: 39. },
+                    }));
+    assert!(base.high_accuracy);
+    assert_relative_eq!(base.latitude.unwrap(), 36.883767, epsilon = 1e-6);
+
+    let mut asm = AisAssembler::new();
+    let vdm = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::VDM(vdm) => vdm,
+        res => panic!("unexpected result {:?}", res),
+    };
+    asm.add(vdm("!AIVDM,2,1,3,B,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0*3E"))
+        .unwrap();
+    let msg = asm.add(vdm("!AIVDM,2,2,3,B,1@0000000000000,2*55")).unwrap().unwrap();
+    let voyage = match msg.decode().unwrap() {
+        AisReport::StaticVoyage(voyage) => voyage,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((voyage.mmsi, voyage.imo), (369190000, Some(6710932)));
+    assert_eq!(voyage.callsign, Some("WDA9674".to_string()));
+    assert_eq!(voyage.ship_name, Some("MT.MITCHELL".to_string()));
+    assert_eq!(voyage.ship_type, Some(99));
+    let dim = voyage.dimensions.unwrap();
+    assert_eq!((dim.length(), dim.beam()), (180, 20));
+    assert_eq!(voyage.eta,
+               ais::Eta { month: Some(1), day: Some(2), hour: Some(8), minute: Some(0) });
+    assert_eq!(voyage.draught, Some(6.));
+    assert_eq!(voyage.destination, Some("SEATTLE".to_string()));
+
+    let class_b = match decode("!AIVDO,1,1,,,B39i>1000nTu;gQAlBj:wwS5kP06,0*5D") {
+        AisReport::ClassBPosition(class_b) => class_b,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((class_b.message_type, class_b.mmsi), (18, 211570180));
+    assert_eq!((class_b.speed_over_ground, class_b.course_over_ground),
+               (Some(0.3), Some(222.3)));
+    assert_eq!((class_b.true_heading, class_b.timestamp), (None, Some(6)));
+    assert_eq!((class_b.ship_name, class_b.dimensions), (None, None));
+
+    let class_b = match decode("!AIVDM,1,1,,B,C5N3SRgPEnJGEBT>NhWAwwo862PaLELTBJ:V00000000S0D:R220,0*0B") {
+        AisReport::ClassBPosition(class_b) => class_b,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((class_b.message_type, class_b.mmsi), (19, 367059850));
+    assert_eq!(class_b.speed_over_ground, Some(8.7));
+    assert_eq!(class_b.ship_name, Some("CAPT.J.RIMES".to_string()));
+    assert_eq!(class_b.ship_type, Some(70));
+    assert_eq!(class_b.dimensions,
+               Some(ais::Dimensions { to_bow: 5, to_stern: 21, to_port: 4, to_starboard: 4 }));
+
+    let aid = match decode("!AIVDM,1,1,,B,E>jHC=c6:W2h22R`@1:WdP00000Opa@H?KTcP10888e?N0,0*12") {
+        AisReport::AidToNavigation(aid) => aid,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!((aid.mmsi, aid.aid_type), (992351030, 22));
+    assert_eq!(aid.name, Some("LUNE DEEP BUOY".to_string()));
+    assert_relative_eq!(aid.latitude.unwrap(), 53.93466, epsilon = 1e-6);
+    assert!(!aid.off_position && !aid.virtual_aid);
+
+    match decode("!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D") {
+        AisReport::ClassBStatic(data) => {
+            assert_eq!((data.mmsi, data.part_number), (271041815, 0));
+            assert_eq!(data.ship_name, Some("PROGUY".to_string()));
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    match decode("!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40") {
+        AisReport::ClassBStatic(data) => {
+            assert_eq!((data.part_number, data.ship_type), (1, Some(60)));
+            assert_eq!(data.vendor_id, Some("1D0".to_string()));
+            assert_eq!(data.callsign, Some("TC6163".to_string()));
+            assert_eq!(data.dimensions.map(|v| v.length()), Some(15));
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let bits = ais::dearmor(b"15M67FC000G?ufbE`FepT@3n00Sa", 0).unwrap();
+    assert_eq!(bits.signed(42, 8), Some(0));
+    assert_eq!(bits.signed(61, 28), Some(-73404971));
+    let msg = AisAssembler::new().add(vdm("!AIVDM,1,1,,A,15M67F,0*28")).unwrap().unwrap();
+    assert_eq!(msg.decode(), Err(AisError::TooShort { message_type: 1, len: 36 }));
+}
+
+#[test]
+fn test_txt_assembler() {
+    let txt = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::TXT(txt) => txt,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let data = txt("$GPTXT,01,01,02,ANTSTATUS=OK*3B");
+    assert_eq!((data.number_of_sentences, data.sentence_num, data.text_id), (1, 1, 2));
+    assert_eq!(data.severity(), TxtSeverity::Notice);
+    assert_eq!(data.text, "ANTSTATUS=OK");
+
+    let mut asm = TxtAssembler::new();
+    let msg = asm.add(data).unwrap().unwrap();
+    assert_eq!(msg.antenna_status(), Some(AntennaStatus::Ok));
+    let msg = asm.add(txt("$GPTXT,01,01,01,ANTENNA OPEN*25")).unwrap().unwrap();
+    assert_eq!(msg.severity, TxtSeverity::Warning);
+    assert_eq!(msg.antenna_status(), Some(AntennaStatus::Open));
+
+    assert_eq!(asm.add(txt("$GPTXT,02,01,00,ROM CORE 1.00 (59842) Jun 27 2012 17:43:52,*74")),
+               Ok(None));
+    let msg = asm.add(txt("$GPTXT,02,02,00, PROTVER 14.00*3C")).unwrap().unwrap();
+    assert_eq!(msg.severity, TxtSeverity::Error);
+    assert_eq!(msg.text, "ROM CORE 1.00 (59842) Jun 27 2012 17:43:52, PROTVER 14.00");
+    assert_eq!(msg.antenna_status(), None);
+    // second part without the first one
+    assert_eq!(asm.add(txt("$GPTXT,02,02,00,lost*4B")),
+               Err(TxtError::MissingPage { expected: 1, received: 2 }));
+    // a lost sentence in the middle drops the message
+    assert_eq!(asm.add(txt("$GPTXT,03,01,00,first*37")), Ok(None));
+    assert_eq!(asm.add(txt("$GPTXT,03,03,00,third*2C")),
+               Err(TxtError::MissingPage { expected: 2, received: 3 }));
+    assert_eq!(asm.add(txt("$GPTXT,03,02,00,second*5E")),
+               Err(TxtError::MissingPage { expected: 1, received: 2 }));
+
+    assert_eq!(AntennaStatus::from_text("ANTSTATUS=SHORT"), Some(AntennaStatus::Short));
+    assert_eq!(AntennaStatus::from_text("ANTSTATUS=DONTKNOW"), Some(AntennaStatus::Unknown));
+}
+
+#[test]
+fn test_dtm_datum_correction() {
+    let dtm = match parse(b"$GPDTM,999,CH,0.08,N,0.07,E,-47.7,W84*10").unwrap() {
+        ParseResult::DTM(dtm) => dtm,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(dtm.local_datum, Datum::UserDefined);
+    assert_eq!(dtm.subdivision.as_deref(), Some("CH"));
+    assert_relative_eq!(dtm.lat_offset.unwrap(), 0.08 / 60.);
+    assert_relative_eq!(dtm.lon_offset.unwrap(), 0.07 / 60.);
+    assert_relative_eq!(dtm.alt_offset.unwrap(), -47.7);
+    assert_eq!(dtm.reference_datum, Some(Datum::Wgs84));
+    assert_eq!(dtm.position_datum(), Datum::Wgs84);
+
+    let mut nmea = Nmea::new();
+    nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+        .unwrap();
+    assert_eq!(nmea.datum, None);
+    assert_eq!(nmea.parse("$GPDTM,999,CH,0.08,N,0.07,E,-47.7,W84*10").unwrap(),
+               SentenceType::DTM);
+    nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+        .unwrap();
+    assert_eq!(nmea.datum, Some(Datum::Wgs84));
+    assert_relative_eq!(nmea.latitude().unwrap(), 53. + 21.6002 / 60.);
+    assert_relative_eq!(nmea.longitude().unwrap(), -(6. + 30.4072 / 60.));
+    assert_relative_eq!(nmea.altitude().unwrap(), 109.4);
+
+    // offsets are unknown, position stays in local datum
+    nmea.parse("$GPDTM,P90,,,,,,,W84*48").unwrap();
+    nmea.parse("$GPGLL,5107.0013414,N,11402.3279144,W,205412.00,A,A*73").unwrap();
+    assert_eq!(nmea.datum, Some(Datum::Pe90));
+    assert_relative_eq!(nmea.latitude().unwrap(), 51. + 7.0013414 / 60.);
+
+    nmea.parse("$GPDTM,W84,,0.0,N,0.0,E,0.0,W84*6F").unwrap();
+    nmea.parse("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68").unwrap();
+    assert_eq!(nmea.datum, Some(Datum::Wgs84));
+    assert_relative_eq!(nmea.latitude().unwrap(), 49. + 16.45 / 60.);
+}
+
+#[test]
+fn test_dtm_kept_across_epochs() {
+    let mut nmea = Nmea::new();
+    nmea.parse_for_fix(b"$GPDTM,999,CH,0.08,N,0.07,E,-47.7,W84*10").unwrap();
+    nmea.parse_for_fix(b"$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+        .unwrap();
+    assert_eq!(nmea.datum, Some(Datum::Wgs84));
+    assert_relative_eq!(nmea.latitude().unwrap(), 53. + 21.6002 / 60.);
+
+    nmea.parse_for_fix(b"$GPGGA,092751.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*77")
+        .unwrap();
+    assert_eq!(nmea.fix_time.map(|v| v.sec), Some(51.));
+    assert_eq!(nmea.datum, Some(Datum::Wgs84));
+    assert_relative_eq!(nmea.latitude().unwrap(), 53. + 21.6002 / 60.);
+    assert_relative_eq!(nmea.altitude().unwrap(), 109.4);
+}
+
+#[test]
+fn test_grs_residuals() {
+    let grs = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::GRS(grs) => grs,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let gsa = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::GSA(gsa) => gsa,
+        res => panic!("unexpected result {:?}", res),
+    };
+
+    let data = grs("$GPGRS,220320.0,0,-0.8,-0.2,-0.1,-0.2,0.8,0.6,,,,,,*79");
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 22, min: 3, sec: 20. }));
+    assert_eq!(data.mode, GrsMode::UsedInFix);
+    assert_eq!(data.residuals.len(), 12);
+    assert_eq!(&data.residuals[..7],
+               &[Some(-0.8), Some(-0.2), Some(-0.1), Some(-0.2), Some(0.8), Some(0.6), None]);
+    assert_eq!(data.gnss_type, Some(GnssType::Gps));
+    assert_eq!((data.system_id, data.signal_id), (None, None));
+
+    let gps = grs("$GNGRS,104148.00,1,2.6,2.2,-1.6,-1.1,-1.7,-1.5,5.8,1.7,,,,,1,1*52");
+    assert_eq!(gps.mode, GrsMode::Recomputed);
+    assert_eq!(gps.gnss_type, Some(GnssType::Gps));
+    assert_eq!((gps.system_id, gps.signal_id), (Some(1), Some(1)));
+    let glonass = grs("$GNGRS,104148.00,1,,0.0,2.1,2.2,2.8,4.0,,,,,,,2,1*78");
+    assert_eq!(glonass.gnss_type, Some(GnssType::Glonass));
+    assert_eq!(glonass.residuals[0], None);
+    let galileo = grs("$GNGRS,104148.00,1,1.5,,,,,,,,,,,,3,7*76");
+    assert_eq!(galileo.residuals.len(), 12);
+    assert_eq!(&galileo.residuals[..2], &[Some(1.5), None]);
+    assert_eq!((galileo.system_id, galileo.signal_id), (Some(3), Some(7)));
+    let empty = grs("$GNGRS,104148.00,1,,,,,,,,,,,,,3,7*5C");
+    assert_eq!(empty.residuals, vec![None; 12]);
+    assert_eq!(empty.gnss_type, Some(GnssType::Galileo));
+    // residual fields are never omitted, IDs must not be taken for residuals
+    assert!(parse(b"$GNGRS,104148.00,1,1.5,-0.5,3,7*70").is_err());
+
+    let gps_gsa = gsa("$GNGSA,A,3,23,02,26,13,29,15,05,21,,,,,1.73,0.85,1.51,1*00");
+    let glonass_gsa = gsa("$GNGSA,A,3,75,86,87,85,77,76,,,,,,,1.73,0.85,1.51,2*00");
+    assert_eq!(&gps.satellite_residuals(&gps_gsa)[..3],
+               &[(23, 2.6), (2, 2.2), (26, -1.6)]);
+    assert!(gps.satellite_residuals(&glonass_gsa).is_empty());
+    assert_eq!(glonass.satellite_residuals(&glonass_gsa),
+               vec![(86, 0.0), (87, 2.1), (85, 2.2), (77, 2.8), (76, 4.0)]);
+
+    let mut stats = ResidualStatistics::new();
+    assert_eq!(stats.add(&gps, &gps_gsa), 8);
+    assert_eq!(stats.add(&glonass, &glonass_gsa), 5);
+    assert_eq!(stats.add(&grs("$GNGRS,104149.00,1,-2.6,2.2,-1.6,-1.1,-1.7,-1.5,5.8,1.7,,,,,1,1*7E"),
+                         &gps_gsa),
+               8);
+    let sat = stats.satellite(&GnssType::Gps, 23).unwrap();
+    assert_eq!((sat.prn, sat.count), (23, 2));
+    assert_relative_eq!(sat.mean, 0.);
+    assert_relative_eq!(sat.rms, 2.6);
+    assert_relative_eq!(sat.std_dev, 2.6);
+    assert_relative_eq!(sat.max_abs, 2.6);
+    let sat = stats.satellite(&GnssType::Glonass, 22).unwrap();
+    assert_eq!((sat.prn, sat.count), (86, 1));
+    assert_eq!(stats.satellites().count(), 13);
+    let outliers: Vec<_> = stats.outliers(3.).map(|v| (v.gnss_type, v.prn)).collect();
+    assert_eq!(outliers, vec![(GnssType::Gps, 5), (GnssType::Glonass, 76)]);
+
+    // GSA with empty fields between satellites, residuals keep their fields
+    let gapped_gsa = gsa("$GPGSA,A,3,,,,,,16,18,,22,24,,,3.6,2.1,2.2*3C");
+    let gapped = grs("$GPGRS,220321.0,0,,,,,,0.5,-0.3,,1.2,-0.9,,*73");
+    assert_eq!(gapped.satellite_residuals(&gapped_gsa),
+               vec![(16, 0.5), (18, -0.3), (22, 1.2), (24, -0.9)]);
+    let mut stats = ResidualStatistics::new();
+    assert_eq!(stats.add(&gapped, &gapped_gsa), 4);
+    assert_relative_eq!(stats.satellite(&GnssType::Gps, 22).unwrap().mean, 1.2);
+}
+
+#[test]
+fn test_parse_rot_rsa() {
+    match parse(b"$HEROT,-11.23,A*07").unwrap() {
+        ParseResult::ROT(rot) => {
+            assert_eq!(rot,
+                       RotData {
+                           rate_of_turn: Some(-11.23),
+                           valid: true,
+                       })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$HEROT,,V*12").unwrap() {
+        ParseResult::ROT(rot) => {
+            assert_eq!(rot,
+                       RotData {
+                           rate_of_turn: None,
+                           valid: false,
+                       })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    match parse(b"$IIRSA,10.5,A,,V*4D").unwrap() {
+        ParseResult::RSA(rsa) => {
+            assert_eq!(rsa,
+                       RsaData {
+                           starboard: Some(10.5),
+                           starboard_valid: true,
+                           port: None,
+                           port_valid: false,
+                       })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$IIRSA,-3.2,A,-3.0,A*42").unwrap() {
+        ParseResult::RSA(rsa) => {
+            assert_eq!((rsa.starboard, rsa.port), (Some(-3.2), Some(-3.0)));
+            assert!(rsa.starboard_valid && rsa.port_valid);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let mut nmea = Nmea::new();
+    assert_eq!(nmea.parse("$HEROT,-11.23,A*07").unwrap(), SentenceType::ROT);
+    assert_eq!(nmea.parse("$IIRSA,10.5,A,,V*4D").unwrap(), SentenceType::RSA);
+}
+
+#[test]
+fn test_parse_xdr() {
+    let xdr = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::XDR(xdr) => xdr,
+        res => panic!("unexpected result {:?}", res),
+    };
+
+    let data = xdr("$HCXDR,A,171,D,PITCH,A,-37,D,ROLL,G,367,,MAGX,G,2420,,MAGY,G,-8984,,MAGZ*41");
+    assert_eq!(data.measurements.len(), 5);
+    assert_eq!(data.measurements[1],
+               XdrMeasurement {
+                   transducer_type: TransducerType::Angular,
+                   value: Some(-37.),
+                   unit: Some(TransducerUnit::Degrees),
+                   name: Some("ROLL".into()),
+               });
+    assert_eq!(data.measurement("MAGZ").and_then(|v| v.value), Some(-8984.));
+    assert_eq!(data.measurement("MAGZ").and_then(|v| v.unit), None);
+    assert_eq!(data.measurements_of(TransducerType::Generic).count(), 3);
+    assert_eq!(data.get(XdrQuantity::Pitch), Some(171.));
+    assert_eq!(data.get(XdrQuantity::Roll), Some(-37.));
+    assert_eq!(data.get(XdrQuantity::AirTemperature), None);
+
+    let data = xdr("$WIXDR,C,19.52,C,TempAir,P,1.02481,B,Barometer,H,62.5,P,Humidity*5E");
+    assert_eq!(data.measurements[2].unit, Some(TransducerUnit::Percent));
+    assert_eq!(data.get(XdrQuantity::AirTemperature), Some(19.52));
+    assert_eq!(data.get(XdrQuantity::BarometricPressure), Some(1.02481));
+    assert_eq!(data.get(XdrQuantity::Humidity), Some(62.5));
+
+    let data = xdr("$IIXDR,P,101325,P,BARO,U,12.6,V,BATT,T,1850,R,ENGINE#0,C,,C,WaterTemp*21");
+    assert_eq!(data.measurements[0].unit, Some(TransducerUnit::Pascals));
+    assert_relative_eq!(data.get(XdrQuantity::BarometricPressure).unwrap(), 1.01325);
+    assert_eq!(data.get(XdrQuantity::BatteryVoltage), Some(12.6));
+    assert_eq!(data.measurement("ENGINE#0").map(|v| (v.transducer_type, v.unit)),
+               Some((TransducerType::Tachometer, Some(TransducerUnit::Rpm))));
+    assert_eq!(data.measurement("WaterTemp").map(|v| v.value), Some(None));
+}
+
+#[test]
+fn test_parse_mtw_mda() {
+    match parse(b"$YXMTW,17.75,C*26").unwrap() {
+        ParseResult::MTW(mtw) => assert_eq!(mtw, MtwData { temperature: Some(17.75) }),
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$YXMTW,,C*0C").unwrap() {
+        ParseResult::MTW(mtw) => assert_eq!(mtw, MtwData { temperature: None }),
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let line = "$WIMDA,30.2269,I,1.0236,B,17.7,C,,,43.3,,5.0,C,131.5,T,134.6,M,0.8,N,0.4,M*59";
+    match parse(line.as_bytes()).unwrap() {
+        ParseResult::MDA(mda) => {
+            assert_eq!(mda,
+                       MdaData {
+                           pressure_inches: Some(30.2269),
+                           pressure_bars: Some(1.0236),
+                           air_temperature: Some(17.7),
+                           water_temperature: None,
+                           relative_humidity: Some(43.3),
+                           absolute_humidity: None,
+                           dew_point: Some(5.0),
+                           wind_direction_true: Some(131.5),
+                           wind_direction_magnetic: Some(134.6),
+                           wind_speed_knots: Some(0.8),
+                           wind_speed_mps: Some(0.4),
+                       })
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    match parse(b"$WIMDA,,I,,B,-2.5,C,,C,,,,C,,T,,M,,N,,M*00").unwrap() {
+        ParseResult::MDA(mda) => {
+            assert_eq!(mda.air_temperature, Some(-2.5));
+            assert_eq!(mda.pressure_bars, None);
+            assert_eq!(mda.wind_speed_mps, None);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let mut nmea = Nmea::new();
+    assert_eq!(nmea.parse(line).unwrap(), SentenceType::MDA);
+}
+
+#[test]
+fn test_target_table() {
+    let ttm = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::TTM(ttm) => ttm,
+        res => panic!("unexpected result {:?}", res),
+    };
+    let tll = |line: &str| match parse(line.as_bytes()).unwrap() {
+        ParseResult::TLL(tll) => tll,
+        res => panic!("unexpected result {:?}", res),
+    };
+
+    let data = ttm("$RATTM,11,25.3,13.7,T,7.0,20.0,T,10.1,-20.0,N,THEM,T,,110025.00,A*0F");
+    assert_eq!(data,
+               TtmData {
+                   target_number: 11,
+                   distance: Some(25.3),
+                   bearing: Some(Bearing::True(13.7)),
+                   speed: Some(7.0),
+                   course: Some(Bearing::True(20.0)),
+                   cpa_distance: Some(10.1),
+                   cpa_time: Some(-20.0),
+                   name: Some("THEM".into()),
+                   status: Some(TargetStatus::Tracking),
+                   reference_target: false,
+                   fix_time: Some(NaiveTime { hour: 11, min: 0, sec: 25. }),
+                   acquisition: Some(TargetAcquisition::Automatic),
+               });
+    // NMEA 2.x, kilometers
+    let data = ttm("$RATTM,02,1.85,90.0,R,18.52,180.0,T,0.5,12.5,K,,Q,*5D");
+    assert_relative_eq!(data.distance.unwrap(), 1.85 / 1.852);
+    assert_relative_eq!(data.speed.unwrap(), 10.);
+    assert_eq!(data.bearing, Some(Bearing::Relative(90.)));
+    assert_eq!((data.name, data.status, data.fix_time),
+               (None, Some(TargetStatus::Acquiring), None));
+
+    let data = tll("$RATLL,01,5603.370,N,00948.347,E,ALPHA,015200.36,T,*7D");
+    assert_relative_eq!(data.latitude.unwrap(), 56. + 3.370 / 60.);
+    assert_relative_eq!(data.longitude.unwrap(), 9. + 48.347 / 60.);
+    assert_eq!(data.name.as_deref(), Some("ALPHA"));
+    assert_eq!(data.fix_time, Some(NaiveTime { hour: 1, min: 52, sec: 0.36 }));
+    assert_eq!((data.status, data.reference_target), (Some(TargetStatus::Tracking), false));
+
+    let osd = match parse(b"$RAOSD,35.1,A,36.0,P,10.2,P,15.3,0.1,N*41").unwrap() {
+        ParseResult::OSD(osd) => osd,
+        res => panic!("unexpected result {:?}", res),
+    };
+    assert_eq!(osd,
+               OsdData {
+                   heading: Some(35.1),
+                   heading_valid: true,
+                   course: Some(36.0),
+                   course_reference: Some(SpeedReference::Positioning),
+                   speed: Some(10.2),
+                   speed_reference: Some(SpeedReference::Positioning),
+                   set: Some(15.3),
+                   drift: Some(0.1),
+               });
+
+    let mut table = TargetTable::new(2);
+    table.update_own_ship(osd);
+    table.update_tll(data);
+    assert_eq!(table.target_position(1, (56., 9.)),
+               Some((56. + 3.370 / 60., 9. + 48.347 / 60.)));
+    let target = table.update_ttm(ttm("$RATTM,01,1.0,0.0,T,7.0,20.0,T,0.5,3.0,N,,T,*5B"));
+    assert_eq!(target.name.as_deref(), Some("ALPHA"));
+    assert_eq!(target.distance, Some(1.0));
+    // the target moved since TLL
+    assert_eq!((target.latitude, target.longitude), (None, None));
+    table.update_ttm(ttm("$RATTM,02,1.85,90.0,R,18.52,180.0,T,0.5,12.5,K,,Q,*5D"));
+    assert_eq!(table.targets().map(|v| v.target_number).collect::<Vec<_>>(), vec![1, 2]);
+
+    // relative bearing 90 turned by heading 35.1
+    let (lat, lon) = table.target_position(2, (56., 9.)).unwrap();
+    let (distance, bearing) = (1.85 / 1.852, 125.1f64.to_radians());
+    assert_relative_eq!(lat, 56. + distance * bearing.cos() / 60., epsilon = 1e-6);
+    assert_relative_eq!(lon,
+                        9. + distance * bearing.sin() / 60. / 56f64.to_radians().cos(),
+                        epsilon = 1e-6);
+    let (lat, lon) = table.target_position(1, (56., 9.)).unwrap();
+    assert_relative_eq!(lat, 56. + 1. / 60., epsilon = 1e-6);
+    assert_relative_eq!(lon, 9., epsilon = 1e-6);
+    table.update_tll(tll("$RATLL,01,5603.370,N,00948.347,E,ALPHA,015200.36,T,*7D"));
+    assert_eq!(table.target_position(1, (56., 9.)),
+               Some((56. + 3.370 / 60., 9. + 48.347 / 60.)));
+
+    assert!(table.tick().is_empty());
+    table.update_ttm(ttm("$RATTM,02,,,,,,,,,N,,L,*72"));
+    assert_eq!(table.tick(), vec![2]);
+    assert_eq!(table.target(1).unwrap().age, 2);
+    assert_eq!(table.tick(), vec![1]);
+    assert!(table.target(1).is_none());
+}
@@ -13,3 +13,9 @@ pub struct NaiveTime {
     pub min: u32,
     pub sec: f64
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct NaiveDateTime {
+    pub date: NaiveDate,
+    pub time: NaiveTime
+}
@@ -0,0 +1,92 @@
+//! Assembly of multi-part TXT messages and decoding of antenna status.
+
+use alloc::string::String;
+
+use page::{PageError, PageSequence};
+use parse::{TxtData, TxtSeverity};
+
+/// Complete text message, joined from all its TXT sentences
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxtMessage {
+    pub text_id: u8,
+    pub severity: TxtSeverity,
+    pub text: String,
+}
+
+impl TxtMessage {
+    /// Antenna status, if this message reports it
+    pub fn antenna_status(&self) -> Option<AntennaStatus> {
+        AntennaStatus::from_text(&self.text)
+    }
+}
+
+/// Antenna status reported by receivers with antenna supervisor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AntennaStatus {
+    Ok,
+    /// Open circuit, antenna is not connected
+    Open,
+    /// Short circuit
+    Short,
+    /// Supervisor is initializing
+    Init,
+    /// Supervisor can't tell the status
+    Unknown,
+}
+
+impl AntennaStatus {
+    /// Decode antenna status text: `ANTSTATUS=OK` of u-blox and Quectel
+    /// or `ANTENNA OK` of MediaTek and SiRF receivers.
+    /// `None` if the text is not about antenna status.
+    pub fn from_text(text: &str) -> Option<AntennaStatus> {
+        let status = text.strip_prefix("ANTSTATUS=")
+            .or_else(|| text.strip_prefix("ANTENNA "))?;
+        Some(match status.trim() {
+                 "OK" => AntennaStatus::Ok,
+                 "OPEN" => AntennaStatus::Open,
+                 "SHORT" => AntennaStatus::Short,
+                 "INIT" => AntennaStatus::Init,
+                 _ => AntennaStatus::Unknown,
+             })
+    }
+}
+
+pub type TxtError = PageError;
+
+/// Joins TXT sentences of multi-part messages
+///
+/// Sentences are matched by text identifier. Incomplete messages,
+/// for example when a sentence was lost, are dropped with an error.
+#[derive(Default)]
+pub struct TxtAssembler {
+    cycles: PageSequence<u8, String>,
+}
+
+impl TxtAssembler {
+    pub fn new() -> TxtAssembler {
+        TxtAssembler::default()
+    }
+
+    /// Feed one parsed TXT sentence. Returns the message if this was
+    /// its last sentence.
+    pub fn add(&mut self, data: TxtData) -> Result<Option<TxtMessage>, TxtError> {
+        let severity = data.severity();
+        let text = &data.text;
+        let (_, text) = self.cycles
+            .add(data.text_id,
+                 data.sentence_num as u16,
+                 data.number_of_sentences as u16,
+                 String::new,
+                 |cycle| -> Result<(), TxtError> {
+                     cycle.push_str(text);
+                     Ok(())
+                 })?;
+        Ok(text.map(|text| {
+                        TxtMessage {
+                            text_id: data.text_id,
+                            severity,
+                            text,
+                        }
+                    }))
+    }
+}
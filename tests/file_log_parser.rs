@@ -6,7 +6,7 @@ use std::io::{BufReader, BufRead};
 use std::fs::File;
 
 fn err_to_string<E: Error>(e: E) -> String {
-    e.description().to_string()
+    e.to_string()
 }
 
 fn process_file(n: &Path) -> Result<Vec<String>, String> {
@@ -29,7 +29,7 @@ fn test_parse_file_log() {
         .unwrap_or_else(|err| panic!("process file failed with error '{}'", err));
 
     let expected: Vec<_> =
-        BufReader::new(File::open(&Path::new("tests").join("nmea1.log.expected")).unwrap())
+        BufReader::new(File::open(Path::new("tests").join("nmea1.log.expected")).unwrap())
             .lines()
             .map(|v| v.unwrap())
             .collect();